@@ -183,6 +183,35 @@ impl<'tu> Comment<'tu> {
         ).map(CommentChild::from_raw).collect()
     }
 
+    /// Returns the names, directions, and text of the `\param` commands in this comment.
+    pub fn get_param_docs(&self) -> Vec<ParamDoc> {
+        fn get_text(children: &[CommentChild], text: &mut String) {
+            for child in children {
+                match *child {
+                    CommentChild::Text(ref string) => text.push_str(string),
+                    CommentChild::InlineCommand(ref command) => {
+                        text.push(' ');
+                        text.push_str(&command.arguments.join(" "));
+                    },
+                    CommentChild::Paragraph(ref children) => get_text(children, text),
+                    _ => { },
+                }
+            }
+        }
+
+        self.get_children().into_iter().filter_map(|c| {
+            match c {
+                CommentChild::ParamCommand(command) => {
+                    let mut text = String::new();
+                    get_text(&command.children, &mut text);
+                    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                    Some(ParamDoc { name: command.parameter, direction: command.direction, text })
+                },
+                _ => None,
+            }
+        }).collect()
+    }
+
     /// Returns this comment as an HTML string.
     pub fn as_html(&self) -> String {
         unsafe { utility::to_string(clang_FullComment_getAsHTML(self.raw)) }
@@ -294,6 +323,19 @@ impl ParamCommand {
     }
 }
 
+// ParamDoc ______________________________________
+
+/// The documentation for a parameter from a `\param` command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamDoc {
+    /// The name of the parameter.
+    pub name: String,
+    /// The parameter direction, if specified.
+    pub direction: Option<ParameterDirection>,
+    /// The text of the documentation with whitespace normalized.
+    pub text: String,
+}
+
 // TParamCommand _________________________________
 
 /// A `\tparam` command.
@@ -33,17 +33,21 @@ pub mod token;
 pub mod sonar;
 
 use std::cmp;
+use std::env;
 use std::fmt;
+use std::fs;
 use std::hash;
 use std::mem;
+use std::process;
 use std::ptr;
 use std::slice;
-use std::collections::{HashMap};
+use std::vec;
+use std::collections::{hash_map, HashMap, HashSet};
 use std::convert::TryInto;
 use std::ffi::{CString};
 use std::marker::{PhantomData};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{self, AtomicBool};
+use std::sync::atomic::{self, AtomicBool, AtomicUsize};
 
 use clang_sys::*;
 
@@ -51,9 +55,9 @@ use libc::{c_int, c_uint, c_ulong};
 
 use completion::{Completer, CompletionString};
 use diagnostic::{Diagnostic};
-use documentation::{Comment};
+use documentation::{Comment, ParamDoc};
 use source::{File, Module, SourceLocation, SourceRange};
-use token::{Token};
+use token::{SemanticToken, SemanticTokenModifiers, SemanticTokenType, Token, TokenKind};
 use utility::{FromError, Nullable};
 
 mod error;
@@ -63,6 +67,59 @@ pub use self::error::*;
 // Enums
 //================================================
 
+// AddressSpace __________________________________
+
+/// Indicates the OpenCL address space of a type.
+///
+/// The numeric address spaces reported by `libclang` are not stable across targets and versions
+/// of `libclang` so this is a best-effort mapping of the numbering used by `libclang` 5.0 and
+/// later.
+#[cfg(feature="clang_5_0")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AddressSpace {
+    /// The type is in the OpenCL `__private` address space.
+    Private,
+    /// The type is in the OpenCL `__global` address space.
+    Global,
+    /// The type is in the OpenCL `__constant` address space.
+    Constant,
+    /// The type is in the OpenCL `__local` address space.
+    Local,
+    /// The type is in the OpenCL `__generic` address space.
+    Generic,
+    /// The type is in another address space (e.g., `Other(0)` for the default address space).
+    Other(u32),
+}
+
+#[cfg(feature="clang_5_0")]
+impl AddressSpace {
+    //- Constructors -----------------------------
+
+    fn from_raw(raw: usize) -> AddressSpace {
+        match raw {
+            1 => AddressSpace::Global,
+            2 => AddressSpace::Local,
+            3 => AddressSpace::Constant,
+            4 => AddressSpace::Private,
+            5 => AddressSpace::Generic,
+            other => AddressSpace::Other(other as u32),
+        }
+    }
+}
+
+// AstChange _____________________________________
+
+/// A change to a declaration between two translation units.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AstChange<'tu> {
+    /// A declaration that is only present in the second translation unit.
+    Added(Entity<'tu>),
+    /// A declaration that is only present in the first translation unit.
+    Removed(Entity<'tu>),
+    /// A declaration that is present in both translation units but with different tokens.
+    Modified(Entity<'tu>, Entity<'tu>),
+}
+
 // Accessibility _________________________________
 
 /// Indicates the accessibility of a declaration or base class specifier.
@@ -171,6 +228,59 @@ impl CallingConvention {
     }
 }
 
+// CommentAttachment _____________________________
+
+/// Indicates which comments should be considered associated with an AST entity.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CommentAttachment {
+    /// Comments which directly precede the AST entity.
+    Leading,
+    /// Comments which follow the AST entity on the same line.
+    Trailing,
+    /// Leading comments or, if there are none, trailing comments.
+    Any,
+}
+
+// ConstexprKind _________________________________
+
+/// Indicates which of the `constexpr`, `consteval`, or `constinit` specifiers a declaration has.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ConstexprKind {
+    /// The declaration is `constexpr`.
+    Constexpr,
+    /// The declaration is `consteval` (C++20).
+    Consteval,
+    /// The declaration is `constinit` (C++20).
+    Constinit,
+}
+
+// Designator ____________________________________
+
+/// A designator in a designated initializer (e.g., `.x` or `[2]`).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Designator {
+    /// A field designator (e.g., `.x`).
+    Field(String),
+    /// An array index designator (e.g., `[2]`).
+    ArrayIndex(i64),
+}
+
+// EffectiveVisibility ___________________________
+
+/// Indicates whether a declaration is visible outside of the binary it is defined in.
+#[cfg(feature="clang_3_8")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EffectiveVisibility {
+    /// The declaration is exported from the binary it is defined in.
+    Exported,
+    /// The declaration has external linkage but is hidden from other binaries.
+    Hidden,
+    /// The declaration is imported from another binary.
+    Imported,
+    /// The declaration does not have external linkage.
+    Local,
+}
+
 // EntityKind ____________________________________
 
 /// Indicates the categorization of an AST entity.
@@ -1030,6 +1140,198 @@ impl Nullability {
     }
 }
 
+// OverloadedOperator ____________________________
+
+/// Indicates the operator overloaded by an operator function.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OverloadedOperator {
+    /// `operator new`.
+    New,
+    /// `operator delete`.
+    Delete,
+    /// `operator new[]`.
+    ArrayNew,
+    /// `operator delete[]`.
+    ArrayDelete,
+    /// `operator+`.
+    Plus,
+    /// `operator-`.
+    Minus,
+    /// `operator*`.
+    Star,
+    /// `operator/`.
+    Slash,
+    /// `operator%`.
+    Percent,
+    /// `operator^`.
+    Caret,
+    /// `operator&`.
+    Amp,
+    /// `operator|`.
+    Pipe,
+    /// `operator~`.
+    Tilde,
+    /// `operator!`.
+    Exclaim,
+    /// `operator=`.
+    Assign,
+    /// `operator<`.
+    Less,
+    /// `operator>`.
+    Greater,
+    /// `operator+=`.
+    PlusAssign,
+    /// `operator-=`.
+    MinusAssign,
+    /// `operator*=`.
+    StarAssign,
+    /// `operator/=`.
+    SlashAssign,
+    /// `operator%=`.
+    PercentAssign,
+    /// `operator^=`.
+    CaretAssign,
+    /// `operator&=`.
+    AmpAssign,
+    /// `operator|=`.
+    PipeAssign,
+    /// `operator<<`.
+    LessLess,
+    /// `operator>>`.
+    GreaterGreater,
+    /// `operator<<=`.
+    LessLessAssign,
+    /// `operator>>=`.
+    GreaterGreaterAssign,
+    /// `operator==`.
+    EqualEqual,
+    /// `operator!=`.
+    ExclaimEqual,
+    /// `operator<=`.
+    LessEqual,
+    /// `operator>=`.
+    GreaterEqual,
+    /// `operator<=>`.
+    Spaceship,
+    /// `operator&&`.
+    AmpAmp,
+    /// `operator||`.
+    PipePipe,
+    /// `operator++`.
+    PlusPlus,
+    /// `operator--`.
+    MinusMinus,
+    /// `operator,`.
+    Comma,
+    /// `operator->*`.
+    ArrowStar,
+    /// `operator->`.
+    Arrow,
+    /// `operator()`.
+    Call,
+    /// `operator[]`.
+    Subscript,
+    /// `operator co_await`.
+    Coawait,
+}
+
+impl OverloadedOperator {
+    fn from_spelling(spelling: &str) -> Option<Self> {
+        let operator = match spelling {
+            "new" => OverloadedOperator::New,
+            "delete" => OverloadedOperator::Delete,
+            "new[]" => OverloadedOperator::ArrayNew,
+            "delete[]" => OverloadedOperator::ArrayDelete,
+            "+" => OverloadedOperator::Plus,
+            "-" => OverloadedOperator::Minus,
+            "*" => OverloadedOperator::Star,
+            "/" => OverloadedOperator::Slash,
+            "%" => OverloadedOperator::Percent,
+            "^" => OverloadedOperator::Caret,
+            "&" => OverloadedOperator::Amp,
+            "|" => OverloadedOperator::Pipe,
+            "~" => OverloadedOperator::Tilde,
+            "!" => OverloadedOperator::Exclaim,
+            "=" => OverloadedOperator::Assign,
+            "<" => OverloadedOperator::Less,
+            ">" => OverloadedOperator::Greater,
+            "+=" => OverloadedOperator::PlusAssign,
+            "-=" => OverloadedOperator::MinusAssign,
+            "*=" => OverloadedOperator::StarAssign,
+            "/=" => OverloadedOperator::SlashAssign,
+            "%=" => OverloadedOperator::PercentAssign,
+            "^=" => OverloadedOperator::CaretAssign,
+            "&=" => OverloadedOperator::AmpAssign,
+            "|=" => OverloadedOperator::PipeAssign,
+            "<<" => OverloadedOperator::LessLess,
+            ">>" => OverloadedOperator::GreaterGreater,
+            "<<=" => OverloadedOperator::LessLessAssign,
+            ">>=" => OverloadedOperator::GreaterGreaterAssign,
+            "==" => OverloadedOperator::EqualEqual,
+            "!=" => OverloadedOperator::ExclaimEqual,
+            "<=" => OverloadedOperator::LessEqual,
+            ">=" => OverloadedOperator::GreaterEqual,
+            "<=>" => OverloadedOperator::Spaceship,
+            "&&" => OverloadedOperator::AmpAmp,
+            "||" => OverloadedOperator::PipePipe,
+            "++" => OverloadedOperator::PlusPlus,
+            "--" => OverloadedOperator::MinusMinus,
+            "," => OverloadedOperator::Comma,
+            "->*" => OverloadedOperator::ArrowStar,
+            "->" => OverloadedOperator::Arrow,
+            "()" => OverloadedOperator::Call,
+            "[]" => OverloadedOperator::Subscript,
+            "co_await" => OverloadedOperator::Coawait,
+            _ => return None,
+        };
+        Some(operator)
+    }
+}
+
+// ParameterPassing ______________________________
+
+/// Indicates how a parameter of a type is passed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ParameterPassing {
+    /// The parameter is passed by value.
+    ByValue,
+    /// The parameter is passed by lvalue reference (e.g., `T&`).
+    ByLValueRef,
+    /// The parameter is passed by rvalue reference (e.g., `T&&`).
+    ByRValueRef,
+    /// The parameter is passed by pointer (e.g., `T*`).
+    ByPointer,
+}
+
+// PreprocDirective ______________________________
+
+/// Indicates the kind of a preprocessing directive.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PreprocDirective {
+    /// An `#if` directive.
+    If,
+    /// An `#ifdef` directive.
+    Ifdef,
+    /// An `#ifndef` directive.
+    Ifndef,
+    /// An `#elif` directive.
+    Elif,
+    /// An `#else` directive.
+    Else,
+    /// An `#endif` directive.
+    Endif,
+    /// A `#define` directive.
+    Define,
+    /// An `#undef` directive.
+    Undef,
+    /// An `#include`, `#include_next`, or `#import` directive.
+    Include,
+    /// A `#pragma` directive.
+    Pragma,
+    /// Any other preprocessing directive (e.g., `#error`).
+    Other,
+}
+
 // PrintingPolicyFlag ____________________________
 
 /// Flags for the printing policy.
@@ -1089,6 +1391,19 @@ pub enum PrintingPolicyFlag {
     PrintFullyQualifiedName = 25,
 }
 
+// RecordKind ____________________________________
+
+/// Indicates whether a record is a `struct`, `union`, or `class`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RecordKind {
+    /// The record is a `struct`.
+    Struct,
+    /// The record is a `union`.
+    Union,
+    /// The record is a `class`.
+    Class,
+}
+
 // RefQualifier __________________________________
 
 /// Indicates the ref qualifier of a C++ function or method type.
@@ -1573,7 +1888,7 @@ pub enum TypeKind {
 impl TypeKind {
     fn from_raw(raw: c_int) -> Option<Self> {
         match raw {
-            1..=38 | 101..=175 => Some(unsafe { mem::transmute(raw) }),
+            1..=38 | 101..=176 => Some(unsafe { mem::transmute(raw) }),
             _ => None,
         }
     }
@@ -1583,6 +1898,19 @@ impl TypeKind {
     }
 }
 
+// ValueCategory _________________________________
+
+/// Indicates the value category of an expression.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ValueCategory {
+    /// The expression is an lvalue (e.g., `x` or `*p`).
+    LValue,
+    /// The expression is an xvalue (e.g., `std::move(x)`).
+    XValue,
+    /// The expression is a prvalue (e.g., `1` or `x + 1`).
+    PRValue,
+}
+
 // Visibility ____________________________________
 
 /// Indicates the linker visibility of an AST element.
@@ -1612,6 +1940,88 @@ impl Visibility {
 // Structs
 //================================================
 
+// AstSnapshot ___________________________________
+
+/// An owned snapshot of an AST entity and its descendants.
+///
+/// Unlike an `Entity`, an `AstSnapshot` does not borrow the translation unit it was taken from so
+/// it can be used after the translation unit has been dropped or sent to another thread.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AstSnapshot {
+    /// The categorization of the AST entity.
+    pub kind: EntityKind,
+    /// The name of the AST entity, if any.
+    pub name: Option<String>,
+    /// The USR of the AST entity, if any.
+    pub usr: Option<Usr>,
+    /// The file path, line, and column of the AST entity, if any.
+    pub location: Option<(PathBuf, u32, u32)>,
+    /// The spelling of the type of the AST entity, if any.
+    pub type_: Option<String>,
+    /// The snapshots of the children of the AST entity.
+    pub children: Vec<AstSnapshot>,
+}
+
+impl AstSnapshot {
+    //- Constructors -----------------------------
+
+    /// Constructs a new `AstSnapshot` of the supplied AST entity and its descendants.
+    pub fn new(entity: &Entity) -> AstSnapshot {
+        let location = entity.get_location().map(|l| l.get_file_location()).and_then(|l| {
+            l.file.map(|f| (f.get_path(), l.line, l.column))
+        });
+
+        AstSnapshot {
+            kind: entity.get_kind(),
+            name: entity.get_name(),
+            usr: entity.get_usr(),
+            location,
+            type_: entity.get_type().map(|t| t.get_display_name()),
+            children: entity.get_children().iter().map(AstSnapshot::new).collect(),
+        }
+    }
+}
+
+// ChildrenWalker ________________________________
+
+/// A pausable walk over the descendants of an AST entity.
+///
+/// The descendants are collected in the order they would be visited by `Entity::visit_children`
+/// when the walker is constructed, so the walker is a snapshot of the AST and later pulls do not
+/// call back into `libclang` (the AST entities remain valid since the translation unit outlives the
+/// walker). This allows the descendants to be pulled one at a time across `await` points or event
+/// loop iterations.
+#[derive(Clone, Debug)]
+pub struct ChildrenWalker<'tu> {
+    entities: vec::IntoIter<Entity<'tu>>,
+}
+
+impl<'tu> ChildrenWalker<'tu> {
+    //- Constructors -----------------------------
+
+    /// Constructs a new `ChildrenWalker` over the descendants of the supplied AST entity.
+    pub fn new(entity: &Entity<'tu>) -> ChildrenWalker<'tu> {
+        let mut entities = vec![];
+        entity.visit_children(|e, _| {
+            entities.push(e);
+            EntityVisitResult::Recurse
+        });
+        ChildrenWalker { entities: entities.into_iter() }
+    }
+}
+
+impl<'tu> Iterator for ChildrenWalker<'tu> {
+    type Item = Entity<'tu>;
+
+    fn next(&mut self) -> Option<Entity<'tu>> {
+        self.entities.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entities.size_hint()
+    }
+}
+
 // Clang _________________________________________
 
 type PhantomUnsendUnsync = PhantomData<*mut ()>;
@@ -1658,6 +2068,31 @@ impl Clang {
             Err("an instance of `Clang` already exists".into())
         }
     }
+
+    //- Accessors --------------------------------
+
+    /// Returns whether a new `Clang` can be constructed (i.e., no instance of `Clang` exists).
+    pub fn is_available() -> bool {
+        AVAILABLE.load(atomic::Ordering::SeqCst)
+    }
+
+    //- Mutators ---------------------------------
+
+    /// Allows a new `Clang` to be constructed even if an instance of `Clang` was not dropped (e.g.,
+    /// because it was leaked by a test which panicked).
+    ///
+    /// This is intended for test harnesses which construct and drop `Clang` many times and which
+    /// know that no instance of `Clang` is still in use.
+    ///
+    /// # Safety
+    ///
+    /// No instance of `Clang` may be in use when this function is called or afterwards. Otherwise
+    /// this library could be used from multiple threads simultaneously and, when the `runtime`
+    /// feature is enabled, dropping either instance would unload the `libclang` shared library
+    /// while the other instance is still using it.
+    pub unsafe fn reset_availability() {
+        AVAILABLE.store(true, atomic::Ordering::SeqCst);
+    }
 }
 
 #[cfg(feature="runtime")]
@@ -1705,6 +2140,37 @@ impl CompilationDatabase {
         }
     }
 
+    /// Returns the unique files compiled by the commands in this database.
+    ///
+    /// The file for each command is the filename associated with the command if supported by the
+    /// version of `libclang` in use (3.8 and later) or the last argument passed to the command
+    /// otherwise. Relative files are resolved against the working directory of the command.
+    pub fn get_files(&self) -> Vec<PathBuf> {
+        let ptr = unsafe { clang_CompilationDatabase_getAllCompileCommands(self.ptr) };
+        let commands = match ptr.map(CompileCommands::from_ptr) {
+            Some(commands) => commands,
+            None => return vec![],
+        };
+
+        let mut files = vec![];
+        let mut unique = HashSet::new();
+        for command in commands.get_commands() {
+            #[cfg(feature="clang_3_8")]
+            let file = command.get_filename();
+            #[cfg(not(feature="clang_3_8"))]
+            let file = match command.get_arguments().pop() {
+                Some(argument) => PathBuf::from(argument),
+                None => continue,
+            };
+
+            let file = command.get_directory().join(file);
+            if unique.insert(file.clone()) {
+                files.push(file);
+            }
+        }
+        files
+    }
+
     /// Find the compile commands for the given file.
     pub fn get_compile_commands<P: AsRef<Path>>(&self, path: P) -> Result<CompileCommands, ()> {
         // Presumably this returns null if we can't find the given path?
@@ -1713,6 +2179,56 @@ impl CompilationDatabase {
         let ptr = unsafe { clang_CompilationDatabase_getCompileCommands(self.ptr, path.as_ptr()) };
         ptr.map(CompileCommands::from_ptr).ok_or(())
     }
+
+    /// Parses the file compiled by each command in this database with the supplied index and
+    /// calls the supplied function with the file and the result of parsing it.
+    ///
+    /// The arguments passed to the parser for each command are the arguments of the command
+    /// without the compiler, the file being compiled, `-c`, and `-o` (and its output file). The
+    /// working directory of each command is passed to the parser with `-working-directory` so that
+    /// relative paths in the arguments are resolved correctly.
+    ///
+    /// Since `libclang` may not be used from multiple threads simultaneously (see `Clang`), the
+    /// files are parsed sequentially on the current thread.
+    pub fn parse_all<'i, F: FnMut(&Path, Result<TranslationUnit<'i>, SourceError>)>(
+        &self, index: &'i Index, mut f: F
+    ) {
+        let ptr = unsafe { clang_CompilationDatabase_getAllCompileCommands(self.ptr) };
+        let commands = match ptr.map(CompileCommands::from_ptr) {
+            Some(commands) => commands,
+            None => return,
+        };
+
+        for command in commands.get_commands() {
+            let arguments = command.get_arguments();
+            if arguments.is_empty() {
+                continue;
+            }
+
+            #[cfg(feature="clang_3_8")]
+            let file = command.get_filename();
+            #[cfg(not(feature="clang_3_8"))]
+            let (file, arguments) = {
+                let mut arguments = arguments;
+                (PathBuf::from(arguments.pop().unwrap()), arguments)
+            };
+
+            let directory = command.get_directory();
+            let path = directory.join(&file);
+
+            let mut filtered = vec!["-working-directory".into(), directory.display().to_string()];
+            let mut iter = arguments.into_iter().skip(1);
+            while let Some(argument) = iter.next() {
+                if argument == "-o" {
+                    iter.next();
+                } else if argument != "-c" && directory.join(&argument) != path {
+                    filtered.push(argument);
+                }
+            }
+
+            f(&path, index.parser(&path).arguments(&filtered).parse());
+        }
+    }
 }
 
 impl Drop for CompilationDatabase {
@@ -1883,6 +2399,16 @@ impl<'tu> Entity<'tu> {
         unsafe { clang_getCursorExtent(self.raw).map(|r| SourceRange::from_raw(r, self.tu)) }
     }
 
+    /// Returns the inclusive start of the source range of this AST entity, if any.
+    pub fn get_start_location(&self) -> Option<SourceLocation<'tu>> {
+        self.get_range().map(|r| r.get_start())
+    }
+
+    /// Returns the exclusive end of the source range of this AST entity, if any.
+    pub fn get_end_location(&self) -> Option<SourceLocation<'tu>> {
+        self.get_range().map(|r| r.get_end())
+    }
+
     /// Returns the accessibility of this declaration or base class specifier, if applicable.
     pub fn get_accessibility(&self) -> Option<Accessibility> {
         unsafe {
@@ -1901,6 +2427,137 @@ impl<'tu> Entity<'tu> {
         ).map(|i| i.map(|a| Entity::from_raw(a, self.tu)).collect())
     }
 
+    /// Returns the comment associated with this AST entity using the supplied attachment, if any.
+    ///
+    /// `libclang` does not allow configuring how comments are attached to declarations so this
+    /// method scans the tokens around this AST entity instead and considers comments of any style.
+    ///
+    /// A leading comment is the run of comments which directly precedes this AST entity, excluding
+    /// comments on the same line as the end of the previous sibling of this AST entity. Multiple
+    /// comments in a leading comment are separated by newlines. A trailing comment is the first
+    /// comment which follows this AST entity on the same line, optionally after punctuation (e.g.,
+    /// `;` or `,`).
+    pub fn get_associated_comment(&self, attachment: CommentAttachment) -> Option<String> {
+        match attachment {
+            CommentAttachment::Leading => self.get_leading_comment(),
+            CommentAttachment::Trailing => self.get_following_comment(),
+            CommentAttachment::Any => {
+                self.get_leading_comment().or_else(|| self.get_following_comment())
+            },
+        }
+    }
+
+    fn get_leading_comment(&self) -> Option<String> {
+        let start = self.get_range()?.get_start();
+        let location = start.get_file_location();
+        let file = location.file?;
+
+        let siblings = self.get_lexical_parent().map(|p| p.get_children()).unwrap_or_default();
+        let index = siblings.iter().position(|s| s == self).unwrap_or(0);
+        let previous = index.checked_sub(1).and_then(|i| siblings[i].get_range()).map(|r| {
+            r.get_end().get_file_location()
+        }).filter(|l| l.file == Some(file));
+
+        let from = previous.map_or(0, |l| l.offset);
+        let tokens = SourceRange::new(file.get_offset_location(from), start).tokenize();
+        let mut comments = tokens.iter().filter(|t| {
+            t.get_location().get_file_location().offset < location.offset
+        }).rev().take_while(|t| {
+            let line = t.get_location().get_file_location().line;
+            t.get_kind() == TokenKind::Comment && previous.map_or(true, |l| l.line != line)
+        }).map(|t| t.get_spelling()).collect::<Vec<_>>();
+
+        if !comments.is_empty() {
+            comments.reverse();
+            Some(comments.join("\n"))
+        } else {
+            None
+        }
+    }
+
+    fn get_following_comment(&self) -> Option<String> {
+        let tokens = self.get_tokens_after_on_line();
+        let token = tokens.iter().find(|t| t.get_kind() != TokenKind::Punctuation)?;
+        if token.get_kind() == TokenKind::Comment {
+            Some(token.get_spelling())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the tokens which start at or after the end of this AST entity on the same line.
+    fn get_tokens_after_on_line(&self) -> Vec<Token<'tu>> {
+        let end = match self.get_range() {
+            Some(range) => range.get_end(),
+            None => return vec![],
+        };
+
+        let location = end.get_file_location();
+        let next = match location.file {
+            Some(file) => file.get_location(location.line + 1, 1),
+            None => return vec![],
+        };
+
+        let tokens = SourceRange::new(end, next).tokenize();
+        tokens.into_iter().filter(|t| {
+            let other = t.get_location().get_file_location();
+            other.offset >= location.offset && other.line == location.line
+        }).collect()
+    }
+
+    /// Returns the alignment requested by the `alignas` specifier or `aligned` attribute of this
+    /// declaration, if any (e.g., `16` in `struct alignas(16) S { };`).
+    ///
+    /// `libclang` does not expose the argument of an alignment attribute so it is found by
+    /// tokenizing the attribute. `None` will be returned if no alignment is specified (e.g.,
+    /// `__attribute__((aligned))`) or if the alignment is not an integer literal.
+    #[cfg(feature="clang_9_0")]
+    pub fn get_alignment_attribute(&self) -> Option<usize> {
+        let attribute = self.get_children().into_iter().find(|c| {
+            c.get_kind() == EntityKind::AlignedAttr
+        })?;
+
+        let tokens = attribute.get_range()?.tokenize();
+        let spellings = tokens.iter().map(|t| t.get_spelling()).collect::<Vec<_>>();
+        let spellings = spellings.iter().map(|s| &s[..]).collect::<Vec<_>>();
+        match &spellings[..] {
+            [_, "(", literal, ")", ..] => {
+                let literal = literal.trim_end_matches(|c| "uUlL".contains(c));
+                if let Some(hex) = literal.strip_prefix("0x").or(literal.strip_prefix("0X")) {
+                    usize::from_str_radix(hex, 16).ok()
+                } else {
+                    literal.parse().ok()
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the assembler label of this declaration, if any (e.g., `g` in
+    /// `int f() __asm__("g");`).
+    ///
+    /// An assembler label overrides the mangled name of a declaration.
+    pub fn get_asm_label(&self) -> Option<String> {
+        self.get_children().into_iter()
+            .find(|c| c.get_kind() == EntityKind::AsmLabelAttr)
+            .and_then(|c| c.get_name())
+    }
+
+    /// Returns the attributes attached to this AST entity (e.g., `AnnotateAttr` or `PureAttr`).
+    pub fn get_attributes(&self) -> Vec<Entity<'tu>> {
+        self.get_children().into_iter().filter(|c| c.is_attribute()).collect()
+    }
+
+    /// Returns the annotation of this annotate attribute (e.g., `foo` in
+    /// `__attribute__((annotate("foo")))`), if applicable.
+    pub fn get_annotate_value(&self) -> Option<String> {
+        if self.get_kind() == EntityKind::AnnotateAttr {
+            self.get_display_name()
+        } else {
+            None
+        }
+    }
+
     /// Returns the availability of this AST entity.
     pub fn get_availability(&self) -> Availability {
         Availability::from_raw(unsafe {clang_getCursorAvailability(self.raw) }).unwrap()
@@ -1931,11 +2588,35 @@ impl<'tu> Entity<'tu> {
         unsafe { utility::to_string_option(clang_Cursor_getRawCommentText(self.raw)) }
     }
 
+    /// Returns the text of the comment associated with this AST entity, if any.
+    ///
+    /// If `strip_markers` is `false`, this is the same as `get_comment`. Otherwise, the comment
+    /// delimiters (e.g., `///`, `/**`, and `*/`) and the leading `*`s of the lines of block
+    /// comments are removed, the common indentation of the remaining lines is removed, and leading
+    /// and trailing blank lines are removed.
+    pub fn get_comment_text(&self, strip_markers: bool) -> Option<String> {
+        let comment = self.get_comment()?;
+        if strip_markers {
+            Some(strip_comment_markers(&comment))
+        } else {
+            Some(comment)
+        }
+    }
+
     ///  Returns the parsed comment associated with this declaration, if applicable.
     pub fn get_parsed_comment(&self) -> Option<Comment<'tu>> {
         unsafe { clang_Cursor_getParsedComment(self.raw).map(Comment::from_raw) }
     }
 
+    /// Returns the documentation for the parameters of this declaration from the `\param`
+    /// commands in its parsed comment.
+    ///
+    /// If this declaration has no parsed comment or its parsed comment has no `\param` commands,
+    /// the returned `Vec` will be empty.
+    pub fn get_param_docs(&self) -> Vec<ParamDoc> {
+        self.get_parsed_comment().map(|c| c.get_param_docs()).unwrap_or_default()
+    }
+
     /// Returns the brief of the comment associated with this AST entity, if any.
     pub fn get_comment_brief(&self) -> Option<String> {
         unsafe { utility::to_string_option(clang_Cursor_getBriefCommentText(self.raw)) }
@@ -1946,6 +2627,18 @@ impl<'tu> Entity<'tu> {
         unsafe { clang_Cursor_getCommentRange(self.raw).map(|r| SourceRange::from_raw(r, self.tu)) }
     }
 
+    /// Returns the documentation comment which trails this AST entity on the same line, if any
+    /// (e.g., `///< The x.` in `int x; ///< The x.`).
+    ///
+    /// Only comments which start with `///<`, `//!<`, `/**<`, or `/*!<` are considered trailing
+    /// comments.
+    pub fn get_trailing_comment(&self) -> Option<String> {
+        self.get_tokens_after_on_line().iter()
+            .filter(|t| t.get_kind() == TokenKind::Comment)
+            .map(|t| t.get_spelling())
+            .find(|c| ["///<", "//!<", "/**<", "/*!<"].iter().any(|p| c.starts_with(p)))
+    }
+
     /// Returns a completion string for this declaration or macro definition, if applicable.
     pub fn get_completion_string(&self) -> Option<CompletionString> {
         unsafe { clang_getCursorCompletionString(self.raw).map(CompletionString::from_ptr) }
@@ -1976,11 +2669,138 @@ impl<'tu> Entity<'tu> {
         children
     }
 
+    /// Returns the children of this AST entity grouped by their kinds.
+    pub fn get_children_by_kind(&self) -> HashMap<EntityKind, Vec<Entity<'tu>>> {
+        let mut children: HashMap<_, Vec<_>> = HashMap::new();
+        self.visit_children(|c, _| {
+            children.entry(c.get_kind()).or_default().push(c);
+            EntityVisitResult::Continue
+        });
+        children
+    }
+
+    /// Returns the children of this AST entity with the supplied kind.
+    pub fn get_children_of_kind(&self, kind: EntityKind) -> Vec<Entity<'tu>> {
+        self.get_children_matching(|k| k == kind)
+    }
+
+    /// Returns the children of this AST entity with kinds for which the supplied predicate returns
+    /// `true`.
+    pub fn get_children_matching<F: Fn(EntityKind) -> bool>(&self, f: F) -> Vec<Entity<'tu>> {
+        let mut children = vec![];
+        self.visit_children(|c, _| {
+            if f(c.get_kind()) {
+                children.push(c);
+            }
+            EntityVisitResult::Continue
+        });
+        children
+    }
+
+    /// Returns which of the `constexpr`, `consteval`, or `constinit` specifiers this declaration
+    /// has, if any.
+    ///
+    /// `libclang` does not expose these specifiers so they are found by scanning the tokens of this
+    /// declaration which precede its name.
+    pub fn get_constexpr_kind(&self) -> Option<ConstexprKind> {
+        if !self.is_declaration() {
+            return None;
+        }
+
+        let location = self.get_location()?;
+        let tokens = self.get_range()?.tokenize();
+        let mut spellings = tokens.iter().take_while(|t| t.get_location() != location).map(|t| {
+            t.get_spelling()
+        });
+        spellings.find_map(|s| match &s[..] {
+            "constexpr" => Some(ConstexprKind::Constexpr),
+            "consteval" => Some(ConstexprKind::Consteval),
+            "constinit" => Some(ConstexprKind::Constinit),
+            _ => None,
+        })
+    }
+
+    /// Returns the designators of this initializer list entry, if it is a designated initializer
+    /// (e.g., `[.x, [2]]` for `.x[2] = 1`).
+    ///
+    /// `libclang` does not expose designators so they are parsed from the tokens of this AST
+    /// entity. Array index designators which are not integer literals (e.g., `[N]`) are not
+    /// supported and result in `None` being returned.
+    pub fn get_designators(&self) -> Option<Vec<Designator>> {
+        let tokens = self.get_range()?.tokenize();
+        let mut spellings = tokens.iter().map(|t| t.get_spelling());
+
+        let mut designators = vec![];
+        loop {
+            match spellings.next()?.as_str() {
+                "." => designators.push(Designator::Field(spellings.next()?)),
+                "[" => {
+                    designators.push(Designator::ArrayIndex(spellings.next()?.parse().ok()?));
+                    if spellings.next()? != "]" {
+                        return None;
+                    }
+                },
+                "=" if !designators.is_empty() => return Some(designators),
+                _ => return None,
+            }
+        }
+    }
+
     /// Returns the AST entity that describes the definition of this AST entity, if any.
     pub fn get_definition(&self) -> Option<Entity<'tu>> {
         unsafe { clang_getCursorDefinition(self.raw).map(|p| Entity::from_raw(p, self.tu)) }
     }
 
+    /// Returns the source range of the definition of this AST entity, if any.
+    ///
+    /// Unlike `get_range`, this returns the range of the definition (including the body) even when
+    /// this AST entity is a declaration such as a method declared in a class and defined
+    /// out-of-line.
+    pub fn get_definition_range(&self) -> Option<SourceRange<'tu>> {
+        self.get_definition().and_then(|d| d.get_range())
+    }
+
+    /// Returns whether this declaration is visible outside of the binary it is defined in.
+    ///
+    /// This combines the linkage, DLL attributes, and linker visibility of this declaration with
+    /// the following precedence:
+    ///
+    /// 1. declarations without external linkage (including those in anonymous namespaces) are
+    ///    `Local`
+    /// 2. declarations with a `dllimport` attribute are `Imported`
+    /// 3. declarations with a `dllexport` attribute are `Exported`
+    /// 4. declarations with hidden linker visibility are `Hidden`
+    /// 5. all other declarations are `Exported`
+    #[cfg(feature="clang_3_8")]
+    pub fn get_effective_visibility(&self) -> EffectiveVisibility {
+        if self.get_linkage() != Some(Linkage::External) {
+            return EffectiveVisibility::Local;
+        }
+
+        let children = self.get_children();
+        if children.iter().any(|c| c.get_kind() == EntityKind::DllImport) {
+            EffectiveVisibility::Imported
+        } else if children.iter().any(|c| c.get_kind() == EntityKind::DllExport) {
+            EffectiveVisibility::Exported
+        } else if self.get_visibility() == Some(Visibility::Hidden) {
+            EffectiveVisibility::Hidden
+        } else {
+            EffectiveVisibility::Exported
+        }
+    }
+
+    /// Returns the names and values of the constants in this enum declaration, if applicable.
+    pub fn get_enum_constants(&self) -> Option<Vec<EnumConstant>> {
+        if self.get_kind() != EntityKind::EnumDecl {
+            return None;
+        }
+
+        Some(self.get_children().into_iter().filter_map(|c| {
+            let (signed, unsigned) = c.get_enum_constant_value()?;
+            Some(EnumConstant { name: c.get_name().unwrap(), signed, unsigned })
+        }).collect())
+    }
+
     /// Returns the value of this enum constant declaration, if applicable.
     pub fn get_enum_constant_value(&self) -> Option<(i64, u64)> {
         unsafe {
@@ -1994,6 +2814,17 @@ impl<'tu> Entity<'tu> {
         }
     }
 
+    /// Returns the name, scopedness, underlying type, and constants of this enum declaration, if
+    /// applicable.
+    pub fn get_enum_info(&self) -> Option<EnumInfo<'tu>> {
+        Some(EnumInfo {
+            name: self.get_name(),
+            is_scoped: self.enum_is_scoped(),
+            underlying_type: self.get_enum_underlying_type()?,
+            constants: self.get_enum_constants()?,
+        })
+    }
+
     /// Returns the underlying type of this enum declaration, if applicable.
     pub fn get_enum_underlying_type(&self) -> Option<Type<'tu>> {
         unsafe { clang_getEnumDeclIntegerType(self.raw).map(|t| Type::from_raw(t, self.tu)) }
@@ -2010,44 +2841,396 @@ impl<'tu> Entity<'tu> {
         }
     }
 
-    /// Returns the `external_source_symbol` attribute attached to this AST entity, if any.
+    /// Returns the types in the dynamic exception specification of this function as spelled in the
+    /// source code paired with the resolved types, if possible (e.g., `int` and `A *` in
+    /// `void f() throw(int, A*);`).
+    ///
+    /// `libclang` does not expose the types in a dynamic exception specification so they are found
+    /// by tokenizing the exception specification. The spellings are the tokens of each type
+    /// separated by spaces where needed (e.g., `const char *` for `const char*`). Names of records,
+    /// enums, and typedefs are resolved by looking them up in the scopes enclosing this function
+    /// among the declarations which precede it. Since `libclang` cannot construct types, builtin,
+    /// pointer, reference, and cv-qualified types can only be resolved if the same type appears
+    /// elsewhere in the translation unit (e.g., as the type of a variable or parameter). Types
+    /// which cannot be resolved are paired with `None`. An empty `Vec` will be returned if this
+    /// function does not have a dynamic exception specification.
     #[cfg(feature="clang_5_0")]
-    pub fn get_external_symbol(&self) -> Option<ExternalSymbol> {
-        unsafe {
-            let mut language = mem::MaybeUninit::uninit();
-            let mut defined = mem::MaybeUninit::uninit();
-            let mut generated: c_uint = 0;
-            if clang_Cursor_isExternalSymbol(self.raw, language.as_mut_ptr(), defined.as_mut_ptr(), &mut generated) != 0 {
-                Some(ExternalSymbol {
-                    language: utility::to_string(language.assume_init()),
-                    defined: utility::to_string(defined.assume_init()),
-                    generated: generated != 0
-                })
-            } else {
-                None
+    pub fn get_exception_types(&self) -> Vec<(String, Option<Type<'tu>>)> {
+        fn get_builtin_kinds(keywords: &[&str]) -> Option<Vec<TypeKind>> {
+            let (mut base, mut signed, mut longs) = ("int", None, 0);
+            for keyword in keywords {
+                match *keyword {
+                    "signed" => signed = Some(true),
+                    "unsigned" => signed = Some(false),
+                    "long" => longs += 1,
+                    "int" => { },
+                    "void" | "bool" | "_Bool" | "char" | "wchar_t" | "char16_t" | "char32_t" |
+                    "short" | "float" | "double" | "__int128" => base = keyword,
+                    _ => return None,
+                }
             }
+
+            let kind = match (base, signed, longs) {
+                ("void", None, 0) => TypeKind::Void,
+                ("bool", None, 0) | ("_Bool", None, 0) => TypeKind::Bool,
+                ("char", None, 0) => return Some(vec![TypeKind::CharS, TypeKind::CharU]),
+                ("char", Some(true), 0) => TypeKind::SChar,
+                ("char", Some(false), 0) => TypeKind::UChar,
+                ("wchar_t", None, 0) => TypeKind::WChar,
+                ("char16_t", None, 0) => TypeKind::Char16,
+                ("char32_t", None, 0) => TypeKind::Char32,
+                ("short", Some(false), 0) => TypeKind::UShort,
+                ("short", _, 0) => TypeKind::Short,
+                ("int", Some(false), 0) => TypeKind::UInt,
+                ("int", _, 0) => TypeKind::Int,
+                ("int", Some(false), 1) => TypeKind::ULong,
+                ("int", _, 1) => TypeKind::Long,
+                ("int", Some(false), 2) => TypeKind::ULongLong,
+                ("int", _, 2) => TypeKind::LongLong,
+                ("__int128", Some(false), 0) => TypeKind::UInt128,
+                ("__int128", _, 0) => TypeKind::Int128,
+                ("float", None, 0) => TypeKind::Float,
+                ("double", None, 0) => TypeKind::Double,
+                ("double", None, 1) => TypeKind::LongDouble,
+                _ => return None,
+            };
+            Some(vec![kind])
         }
-    }
 
-    /// Returns the file included by this inclusion directive, if applicable.
-    pub fn get_file(&self) -> Option<File<'tu>> {
-        unsafe { clang_getIncludedFile(self.raw).map(|f| File::from_ptr(f, self.tu)) }
-    }
+        fn is_identifier_start(c: char) -> bool {
+            c.is_alphabetic() || c == '_'
+        }
 
-    /// Returns the language used by this declaration, if applicable.
-    pub fn get_language(&self) -> Option<Language> {
-        unsafe {
-            match clang_getCursorLanguage(self.raw) {
-                CXLanguage_Invalid => None,
-                other => Language::from_raw(other),
+        fn spell(tokens: &[&str]) -> String {
+            let mut spelling = String::new();
+            for (index, token) in tokens.iter().enumerate() {
+                let glued = index == 0 ||
+                    matches!(*token, "::" | "," | "<" | ">" | ")") ||
+                    matches!(tokens[index - 1], "::" | "<" | "(");
+                if !glued {
+                    spelling.push(' ');
+                }
+                spelling.push_str(token);
             }
+            spelling
         }
-    }
 
-    /// Returns the lexical parent of this AST entity, if any.
-    pub fn get_lexical_parent(&self) -> Option<Entity<'tu>> {
-        unsafe { clang_getCursorLexicalParent(self.raw).map(|p| Entity::from_raw(p, self.tu)) }
-    }
+        fn is_same_unqualified(a: &Type, b: &Type) -> bool {
+            let (a, b) = (a.get_canonical_type(), b.get_canonical_type());
+            if a.get_kind() != b.get_kind() {
+                return false;
+            }
+
+            match a.get_kind() {
+                TypeKind::Pointer | TypeKind::LValueReference | TypeKind::RValueReference => {
+                    a.get_pointee_type() == b.get_pointee_type()
+                },
+                TypeKind::Record | TypeKind::Enum => a.get_declaration() == b.get_declaration(),
+                TypeKind::Void | TypeKind::Bool => true,
+                _ => a.is_integer() || a.is_floating_point(),
+            }
+        }
+
+        if self.get_exception_specification() != Some(ExceptionSpecification::Dynamic) {
+            return vec![];
+        }
+
+        let tokens = self.get_range().map_or(vec![], |r| r.tokenize());
+        let spellings = tokens.iter().map(|t| t.get_spelling()).collect::<Vec<_>>();
+        let start = match spellings.windows(2).position(|w| w[0] == "throw" && w[1] == "(") {
+            Some(start) => start + 2,
+            None => return vec![],
+        };
+
+        let mut entries = vec![vec![]];
+        let mut depth = 0;
+        for spelling in &spellings[start..] {
+            match &spelling[..] {
+                ")" if depth == 0 => break,
+                "," if depth == 0 => {
+                    entries.push(vec![]);
+                    continue;
+                },
+                "(" | "<" => depth += 1,
+                ")" | ">" => depth -= 1,
+                _ => { },
+            }
+            entries.last_mut().unwrap().push(&spelling[..]);
+        }
+
+        let mut scopes = vec![];
+        let mut parent = self.get_semantic_parent();
+        while let Some(p) = parent.filter(|p| p.get_kind() != EntityKind::TranslationUnit) {
+            scopes.push(format!("{}::", p.get_qualified_name()));
+            parent = p.get_semantic_parent();
+        }
+        scopes.push(String::new());
+
+        let mut declarations = HashMap::new();
+        let mut types = vec![];
+        let mut visible = true;
+        self.get_translation_unit().get_entity().visit_children(|e, _| {
+            visible = visible && e != *self;
+            match e.get_kind() {
+                EntityKind::StructDecl | EntityKind::ClassDecl | EntityKind::UnionDecl |
+                EntityKind::EnumDecl | EntityKind::TypedefDecl | EntityKind::TypeAliasDecl
+                    if visible => {
+                    declarations.entry(e.get_qualified_name()).or_insert(e);
+                },
+                _ => { },
+            }
+            types.extend(e.get_type().into_iter().chain(e.get_result_type()).map(|t| {
+                t.get_canonical_type()
+            }));
+            EntityVisitResult::Recurse
+        });
+
+        let find = |f: &dyn Fn(&Type) -> bool| types.iter().find(|t| f(t)).cloned();
+        let qualify = |type_: Type<'tu>, qualifier: &str| -> Option<Type<'tu>> {
+            let const_ = type_.is_const_qualified() || qualifier == "const";
+            let volatile = type_.is_volatile_qualified() || qualifier == "volatile";
+            find(&|t| {
+                is_same_unqualified(t, &type_) &&
+                t.is_const_qualified() == const_ &&
+                t.is_volatile_qualified() == volatile
+            })
+        };
+
+        let resolve = |entry: &[&str]| -> Option<Type<'tu>> {
+            let entry = entry.iter().cloned().filter(|s| {
+                !matches!(*s, "struct" | "class" | "union" | "enum" | "typename")
+            }).collect::<Vec<_>>();
+            let index = entry.iter().position(|s| !matches!(*s, "const" | "volatile"))?;
+            let (qualifiers, rest) = entry.split_at(index);
+            let end = rest.iter().position(|s| {
+                matches!(*s, "*" | "&" | "&&" | "const" | "volatile")
+            }).unwrap_or(rest.len());
+            let (base, declarators) = rest.split_at(end);
+
+            let mut type_ = if let Some(kinds) = get_builtin_kinds(base) {
+                find(&|t| {
+                    kinds.contains(&t.get_kind()) &&
+                    !t.is_const_qualified() &&
+                    !t.is_volatile_qualified()
+                })?
+            } else if base.iter().all(|s| *s == "::" || s.starts_with(is_identifier_start)) {
+                let name = base.concat();
+                let declaration = if let Some(name) = name.strip_prefix("::") {
+                    declarations.get(name)
+                } else {
+                    scopes.iter().filter_map(|s| declarations.get(&format!("{}{}", s, name))).next()
+                };
+                declaration?.get_type()?
+            } else {
+                return None;
+            };
+
+            for qualifier in qualifiers {
+                type_ = qualify(type_, qualifier)?;
+            }
+
+            for declarator in declarators {
+                type_ = match *declarator {
+                    "*" | "&" | "&&" => {
+                        let kind = match *declarator {
+                            "*" => TypeKind::Pointer,
+                            "&" => TypeKind::LValueReference,
+                            _ => TypeKind::RValueReference,
+                        };
+                        let pointee = type_.get_canonical_type();
+                        find(&|t| {
+                            t.get_kind() == kind &&
+                            t.get_pointee_type() == Some(pointee) &&
+                            !t.is_const_qualified() &&
+                            !t.is_volatile_qualified()
+                        })?
+                    },
+                    qualifier => qualify(type_, qualifier)?,
+                };
+            }
+
+            Some(type_)
+        };
+
+        entries.iter().filter(|e| !e.is_empty()).map(|e| (spell(e), resolve(e))).collect()
+    }
+
+    /// Returns the `external_source_symbol` attribute attached to this AST entity, if any.
+    #[cfg(feature="clang_5_0")]
+    pub fn get_external_symbol(&self) -> Option<ExternalSymbol> {
+        unsafe {
+            let mut language = mem::MaybeUninit::uninit();
+            let mut defined = mem::MaybeUninit::uninit();
+            let mut generated: c_uint = 0;
+            if clang_Cursor_isExternalSymbol(self.raw, language.as_mut_ptr(), defined.as_mut_ptr(), &mut generated) != 0 {
+                Some(ExternalSymbol {
+                    language: utility::to_string(language.assume_init()),
+                    defined: utility::to_string(defined.assume_init()),
+                    generated: generated != 0
+                })
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns the `format` attribute of this function declaration, if any (e.g.,
+    /// `__attribute__((format(printf, 1, 2)))`).
+    ///
+    /// `libclang` does not expose the arguments of `format` attributes so they are parsed from the
+    /// tokens of the attributes of this declaration. The archetype is returned without any
+    /// surrounding underscores (e.g., `printf` for `__printf__`).
+    pub fn get_format_attribute(&self) -> Option<FormatAttribute> {
+        self.get_children().into_iter().filter(|c| c.is_attribute()).find_map(|c| {
+            let tokens = c.get_range()?.tokenize();
+            let spellings = tokens.iter().map(|t| t.get_spelling()).collect::<Vec<_>>();
+            match &spellings[..] {
+                [name, l, archetype, c1, index, c2, first, r, ..]
+                    if (name == "format" || name == "__format__") && l == "(" &&
+                       c1 == "," && c2 == "," && r == ")" => Some(FormatAttribute {
+                    archetype: archetype.trim_matches('_').into(),
+                    format_index: index.parse().ok()?,
+                    first_to_check: first.parse().ok()?,
+                }),
+                _ => None,
+            }
+        })
+    }
+
+    /// Returns the file included by this inclusion directive, if applicable.
+    pub fn get_file(&self) -> Option<File<'tu>> {
+        unsafe { clang_getIncludedFile(self.raw).map(|f| File::from_ptr(f, self.tu)) }
+    }
+
+    /// Returns the resolved path of the file included by this inclusion directive, if applicable.
+    ///
+    /// Unlike the spelling returned by `get_inclusion_spelling`, this is the path of the file that
+    /// `libclang` found when resolving the inclusion directive.
+    pub fn get_inclusion_resolved_path(&self) -> Option<PathBuf> {
+        if self.get_kind() != EntityKind::InclusionDirective {
+            return None;
+        }
+
+        self.get_file().map(|f| f.get_path())
+    }
+
+    /// Returns the literal spelling of the file included by this inclusion directive (e.g.,
+    /// `<vector>` or `"header.hpp"`), if applicable.
+    ///
+    /// `libclang` does not expose the spelling of an inclusion directive so it is determined by the
+    /// tokens of this inclusion directive. `None` will be returned if the included file is named
+    /// by a macro.
+    pub fn get_inclusion_spelling(&self) -> Option<String> {
+        if self.get_kind() != EntityKind::InclusionDirective {
+            return None;
+        }
+
+        let tokens = self.get_range()?.tokenize();
+        let index = tokens.iter().position(|t| {
+            let spelling = t.get_spelling();
+            spelling == "<" || spelling.starts_with('"')
+        })?;
+
+        let start = tokens[index];
+        if start.get_spelling() != "<" {
+            return Some(start.get_spelling());
+        }
+
+        let end = tokens[index..].iter().find(|t| t.get_spelling() == ">")?;
+        SourceRange::new(start.get_location(), end.get_range().get_end()).get_text()
+    }
+
+    /// Returns the source range of this AST entity extended to include its leading attributes and
+    /// its associated comment, if any.
+    ///
+    /// The start of the returned range is the earliest of the start of the range of this AST
+    /// entity, the start of the range of the comment associated with this AST entity (see
+    /// `get_comment_range`), and the start of the ranges of the attributes of this AST entity.
+    /// Since the ranges of attributes do not include the surrounding syntax, the start of an
+    /// attribute range is first moved back over any `[[`, `__attribute__((`, or `__declspec(`
+    /// which directly precedes it on the same line.
+    pub fn get_full_range(&self) -> Option<SourceRange<'tu>> {
+        fn extend<'tu>(start: SourceLocation<'tu>) -> SourceLocation<'tu> {
+            let location = start.get_file_location();
+            let file = match location.file {
+                Some(file) => file,
+                None => return start,
+            };
+
+            let tokens = SourceRange::new(file.get_location(location.line, 1), start).tokenize();
+            tokens.iter().rev().filter(|t| {
+                t.get_location().get_file_location().offset < location.offset
+            }).take_while(|t| {
+                let spelling = t.get_spelling();
+                spelling == "[" || spelling == "(" ||
+                spelling == "__attribute__" || spelling == "__declspec"
+            }).last().map_or(start, |t| t.get_location())
+        }
+
+        let range = self.get_range()?;
+        let mut start = range.get_start();
+        let mut location = start.get_file_location();
+
+        let children = self.get_children();
+        let attributes = children.iter().filter(|c| c.is_attribute()).filter_map(|c| {
+            c.get_range().map(|r| extend(r.get_start()))
+        });
+        let comment = self.get_comment_range().map(|r| r.get_start());
+
+        for other in attributes.chain(comment) {
+            let other_location = other.get_file_location();
+            if other_location.file == location.file && other_location.offset < location.offset {
+                start = other;
+                location = other_location;
+            }
+        }
+
+        Some(SourceRange::new(start, range.get_end()))
+    }
+
+    /// Returns the source code covered by the full source range of this AST entity, if possible.
+    ///
+    /// See `get_full_range` and `SourceRange::get_text` for details.
+    pub fn get_full_text(&self) -> Option<String> {
+        self.get_full_range().and_then(|r| r.get_text())
+    }
+
+    /// Returns the template or template partial specialization this template specialization or
+    /// member of a template specialization was instantiated from, if applicable.
+    ///
+    /// The pattern is resolved in the following order:
+    ///
+    /// * class template specializations return the most specialized partial specialization that
+    ///   matched the template arguments or the primary class template if none matched
+    /// * function template specializations return the primary function template
+    /// * members of class template specializations return the member of the class template (or
+    ///   partial specialization) they were instantiated from
+    ///
+    /// Templates and template partial specializations are not instantiated from a pattern so this
+    /// method returns `None` for them even though `get_template` returns their primary template.
+    pub fn get_instantiation_pattern(&self) -> Option<Entity<'tu>> {
+        match self.get_kind() {
+            EntityKind::ClassTemplate |
+            EntityKind::ClassTemplatePartialSpecialization |
+            EntityKind::FunctionTemplate => None,
+            _ => self.get_template(),
+        }
+    }
+
+    /// Returns the language used by this declaration, if applicable.
+    pub fn get_language(&self) -> Option<Language> {
+        unsafe {
+            match clang_getCursorLanguage(self.raw) {
+                CXLanguage_Invalid => None,
+                other => Language::from_raw(other),
+            }
+        }
+    }
+
+    /// Returns the lexical parent of this AST entity, if any.
+    pub fn get_lexical_parent(&self) -> Option<Entity<'tu>> {
+        unsafe { clang_getCursorLexicalParent(self.raw).map(|p| Entity::from_raw(p, self.tu)) }
+    }
 
     /// Returns the linkage of this AST entity, if any.
     pub fn get_linkage(&self) -> Option<Linkage> {
@@ -2059,13 +3242,48 @@ impl<'tu> Entity<'tu> {
         }
     }
 
+    /// Returns the declarations in the body of this function or statement (e.g., local variables,
+    /// typedefs, and records).
+    ///
+    /// The body is searched recursively (e.g., declarations in nested blocks and `for` loops are
+    /// included) but declarations are not searched, so the members of local records are not
+    /// included. If this AST entity is not a statement and does not have a body (e.g., a function
+    /// declaration without a definition), the returned `Vec` will be empty.
+    pub fn get_local_declarations(&self) -> Vec<Entity<'tu>> {
+        let body = if self.is_statement() {
+            Some(*self)
+        } else {
+            self.get_children().into_iter().find(|c| c.get_kind() == EntityKind::CompoundStmt)
+        };
+
+        let mut declarations = vec![];
+        if let Some(body) = body {
+            body.visit_children(|e, _| {
+                if e.is_declaration() {
+                    declarations.push(e);
+                    EntityVisitResult::Continue
+                } else {
+                    EntityVisitResult::Recurse
+                }
+            });
+        }
+        declarations
+    }
+
     /// Returns the mangled name of this AST entity, if any.
+    ///
+    /// The name is mangled for the target of the translation unit containing this AST entity so
+    /// the mangled name for another target requires parsing for that target (e.g., with
+    /// `Parser::target`). Note that an assembler label (see `get_asm_label`) overrides mangling.
     #[cfg(feature="clang_3_6")]
     pub fn get_mangled_name(&self) -> Option<String> {
         unsafe { utility::to_string_option(clang_Cursor_getMangling(self.raw)) }
     }
 
     /// Returns the mangled names of this C++ constructor or destructor, if applicable.
+    ///
+    /// As with `get_mangled_name`, the names are mangled for the target of the translation unit
+    /// containing this AST entity.
     #[cfg(feature="clang_3_8")]
     pub fn get_mangled_names(&self) -> Option<Vec<String>> {
         unsafe { utility::to_string_set_option(clang_Cursor_getCXXManglings(self.raw)) }
@@ -2089,8 +3307,12 @@ impl<'tu> Entity<'tu> {
 
     /// Returns the source ranges of the name of this AST entity.
     pub fn get_name_ranges(&self) -> Vec<SourceRange<'tu>> {
+        self.collect_name_ranges(|i| unsafe { clang_Cursor_getSpellingNameRange(self.raw, i, 0) })
+    }
+
+    fn collect_name_ranges<F: Fn(c_uint) -> CXSourceRange>(&self, f: F) -> Vec<SourceRange<'tu>> {
         unsafe {
-            (0..).map(|i| clang_Cursor_getSpellingNameRange(self.raw, i, 0)).take_while(|r| {
+            (0..).map(f).take_while(|r| {
                 if clang_Range_isNull(*r) != 0 {
                     false
                 } else {
@@ -2120,16 +3342,80 @@ impl<'tu> Entity<'tu> {
         utility::to_string_option(unsafe { clang_Cursor_getObjCPropertyGetterName(self.raw) })
     }
 
+    /// Returns the class extended by this Objective-C category declaration, if applicable.
+    pub fn get_objc_category_class(&self) -> Option<Entity<'tu>> {
+        if self.get_kind() != EntityKind::ObjCCategoryDecl {
+            return None;
+        }
+
+        self.get_children().into_iter()
+            .find(|c| c.get_kind() == EntityKind::ObjCClassRef)
+            .and_then(|c| c.get_reference())
+    }
+
     /// Returns the element type for this Objective-C `iboutletcollection` attribute, if applicable.
     pub fn get_objc_ib_outlet_collection_type(&self) -> Option<Type<'tu>> {
         unsafe { clang_getIBOutletCollectionType(self.raw).map(|t| Type::from_raw(t, self.tu)) }
     }
 
+    /// Returns the Objective-C implementation of this Objective-C class or category declaration,
+    /// if any (e.g., the `@implementation` for an `@interface`).
+    ///
+    /// The implementation is found by searching the top-level AST entities of the translation
+    /// unit for an implementation with the same USR as this declaration.
+    pub fn get_objc_implementation(&self) -> Option<Entity<'tu>> {
+        let kind = match self.get_kind() {
+            EntityKind::ObjCInterfaceDecl => EntityKind::ObjCImplementationDecl,
+            EntityKind::ObjCCategoryDecl => EntityKind::ObjCCategoryImplDecl,
+            _ => return None,
+        };
+        self.find_objc_counterpart(kind)
+    }
+
+    /// Returns the Objective-C class or category declaration for this Objective-C implementation,
+    /// if any (e.g., the `@interface` for an `@implementation`).
+    ///
+    /// The declaration is found by searching the top-level AST entities of the translation unit
+    /// for a declaration with the same USR as this implementation.
+    pub fn get_objc_interface(&self) -> Option<Entity<'tu>> {
+        let kind = match self.get_kind() {
+            EntityKind::ObjCImplementationDecl => EntityKind::ObjCInterfaceDecl,
+            EntityKind::ObjCCategoryImplDecl => EntityKind::ObjCCategoryDecl,
+            _ => return None,
+        };
+        self.find_objc_counterpart(kind)
+    }
+
+    fn find_objc_counterpart(&self, kind: EntityKind) -> Option<Entity<'tu>> {
+        let usr = self.get_usr()?;
+        self.tu.get_entity().get_children().into_iter().find(|c| {
+            c.get_kind() == kind && c.get_usr().as_ref() == Some(&usr)
+        })
+    }
+
     /// Returns the type of the receiver of this Objective-C message, if applicable.
     pub fn get_objc_receiver_type(&self) -> Option<Type<'tu>> {
         unsafe { clang_Cursor_getReceiverType(self.raw).map(|t| Type::from_raw(t, self.tu)) }
     }
 
+    /// Returns the protocols adopted by this Objective-C class, category, or protocol declaration.
+    pub fn get_objc_protocols(&self) -> Vec<Entity<'tu>> {
+        self.get_children().into_iter().filter_map(|c| {
+            if c.get_kind() == EntityKind::ObjCProtocolRef {
+                c.get_reference()
+            } else {
+                None
+            }
+        }).collect()
+    }
+
+    /// Returns the superclass of this Objective-C class declaration, if applicable.
+    pub fn get_objc_superclass(&self) -> Option<Entity<'tu>> {
+        self.get_children().into_iter()
+            .find(|c| c.get_kind() == EntityKind::ObjCSuperClassRef)
+            .and_then(|c| c.get_reference())
+    }
+
     /// Returns the selector index for this Objective-C selector identifier, if applicable.
     pub fn get_objc_selector_index(&self) -> Option<usize> {
         let index = unsafe { clang_Cursor_getObjCSelectorIndex(self.raw) };
@@ -2183,6 +3469,56 @@ impl<'tu> Entity<'tu> {
         }
     }
 
+    /// Returns the functions in the semantic parent of this function or method which share the
+    /// name of this function or method (i.e., the overload set), including this function or method.
+    ///
+    /// Only the first declaration of each function is included. If this AST entity is not a
+    /// function or method, the returned `Vec` will be empty.
+    pub fn get_overload_set(&self) -> Vec<Entity<'tu>> {
+        fn is_function(entity: &Entity) -> bool {
+            matches!(
+                entity.get_kind(),
+                EntityKind::FunctionDecl |
+                EntityKind::FunctionTemplate |
+                EntityKind::Method |
+                EntityKind::Constructor |
+                EntityKind::ConversionFunction
+            )
+        }
+
+        if !is_function(self) {
+            return vec![];
+        }
+
+        let name = self.get_name();
+        let siblings = self.get_semantic_parent().map(|p| p.get_children()).unwrap_or_default();
+
+        let mut canonicals = HashSet::new();
+        siblings.into_iter().filter(|s| {
+            is_function(s) && s.get_name() == name && canonicals.insert(s.get_canonical_entity())
+        }).collect()
+    }
+
+    /// Returns the operator overloaded by this operator function, if applicable.
+    ///
+    /// The operator is determined by the part of the name of this AST entity which follows
+    /// `operator` (e.g., `[]` for `operator[]`). Conversion functions and literal operators are not
+    /// operator overloads and result in `None`.
+    pub fn get_overloaded_operator(&self) -> Option<OverloadedOperator> {
+        let name = self.get_name()?;
+        if !name.starts_with("operator") {
+            return None;
+        }
+
+        let rest = &name["operator".len()..];
+        if rest.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+            return None;
+        }
+
+        let spelling = rest.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+        OverloadedOperator::from_spelling(&spelling)
+    }
+
     /// Returns the methods that were overridden by this method, if applicable.
     pub fn get_overridden_methods(&self) -> Option<Vec<Entity<'tu>>> {
         unsafe {
@@ -2199,6 +3535,22 @@ impl<'tu> Entity<'tu> {
         }
     }
 
+    /// Returns the parameters of this function or method declaration, if applicable.
+    pub fn get_parameters(&self) -> Option<Vec<Parameter<'tu>>> {
+        if !self.is_declaration() {
+            return None;
+        }
+
+        self.get_arguments().map(|a| a.into_iter().enumerate().map(|(index, a)| {
+            Parameter {
+                name: a.get_name(),
+                type_: a.get_type().unwrap(),
+                default_argument: a.get_children().into_iter().find(|c| c.is_expression()),
+                index,
+            }
+        }).collect())
+    }
+
     /// Returns the availability of this declaration on the platforms where it is known, if
     /// applicable.
     pub fn get_platform_availability(&self) -> Option<Vec<PlatformAvailability>> {
@@ -2221,17 +3573,172 @@ impl<'tu> Entity<'tu> {
         }
     }
 
+    /// Returns the qualified name of this AST entity (e.g., `a::B::c`).
+    ///
+    /// The qualified name is made up of the names of this AST entity and its semantic parents
+    /// separated by `::`. Unnamed AST entities (e.g., anonymous namespaces) are omitted. See
+    /// `QualifiedNameCache` for computing the qualified names of many AST entities.
+    pub fn get_qualified_name(&self) -> String {
+        let mut names = vec![];
+        let mut entity = Some(*self);
+        while let Some(e) = entity {
+            if e.get_kind() == EntityKind::TranslationUnit {
+                break;
+            }
+
+            names.extend(e.get_name());
+            entity = e.get_semantic_parent();
+        }
+        names.reverse();
+        names.join("::")
+    }
+
+    /// Returns the kind of this preprocessing directive, if applicable.
+    ///
+    /// `libclang` does not expose the kinds of preprocessing directives so the kind is determined
+    /// by the token which follows the `#` at the start of the line this preprocessing directive
+    /// begins on. Macro expansions are not preprocessing directives and result in `None`.
+    pub fn get_preprocessing_directive_kind(&self) -> Option<PreprocDirective> {
+        if !self.is_preprocessing() || self.get_kind() == EntityKind::MacroExpansion {
+            return None;
+        }
+
+        let range = self.get_range()?;
+        let location = range.get_start().get_file_location();
+        let start = location.file?.get_location(location.line, 1);
+        let tokens = SourceRange::new(start, range.get_end()).tokenize();
+        let spellings = tokens.iter().take(2).map(|t| t.get_spelling()).collect::<Vec<_>>();
+        match &spellings[..] {
+            [hash, name] if hash == "#" => Some(match &name[..] {
+                "if" => PreprocDirective::If,
+                "ifdef" => PreprocDirective::Ifdef,
+                "ifndef" => PreprocDirective::Ifndef,
+                "elif" => PreprocDirective::Elif,
+                "else" => PreprocDirective::Else,
+                "endif" => PreprocDirective::Endif,
+                "define" => PreprocDirective::Define,
+                "undef" => PreprocDirective::Undef,
+                "include" | "include_next" | "import" => PreprocDirective::Include,
+                "pragma" => PreprocDirective::Pragma,
+                _ => PreprocDirective::Other,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns whether this record declaration is a `struct`, `union`, or `class`, if applicable.
+    pub fn get_record_kind(&self) -> Option<RecordKind> {
+        match self.get_kind() {
+            EntityKind::StructDecl => Some(RecordKind::Struct),
+            EntityKind::UnionDecl => Some(RecordKind::Union),
+            EntityKind::ClassDecl => Some(RecordKind::Class),
+            _ => None,
+        }
+    }
+
     /// Returns the AST entity referred to by this AST entity, if any.
     pub fn get_reference(&self) -> Option<Entity<'tu>> {
         unsafe { clang_getCursorReferenced(self.raw).map(|p| Entity::from_raw(p, self.tu)) }
     }
 
+    /// Returns the source ranges of the name of the AST entity referred to by this reference.
+    ///
+    /// * `qualifier` - whether to include the nested-name-specifier (e.g., `A::B::` in `A::B::c`)
+    /// * `template_args` - whether to include the explicit template arguments (e.g., `<int>`)
+    /// * `single_piece` - whether to return a single source range instead of multiple source
+    ///   ranges for names which are split into pieces (e.g., `operator[]`)
+    pub fn get_reference_name_ranges(
+        &self, qualifier: bool, template_args: bool, single_piece: bool
+    ) -> Vec<SourceRange<'tu>> {
+        let mut flags = 0;
+        if qualifier {
+            flags |= CXNameRange_WantQualifier;
+        }
+        if template_args {
+            flags |= CXNameRange_WantTemplateArgs;
+        }
+        if single_piece {
+            flags |= CXNameRange_WantSinglePiece;
+        }
+        self.collect_name_ranges(|i| unsafe {
+            clang_getCursorReferenceNameRange(self.raw, flags, i)
+        })
+    }
+
+    /// Returns the AST entities in the translation unit containing this AST entity which refer to
+    /// this AST entity (e.g., `DeclRefExpr` and `MemberRefExpr` entities).
+    ///
+    /// This visits every AST entity in the translation unit so it runs in O(n) time. Prefer
+    /// `File::get_references` when only the references in a single file are needed.
+    pub fn get_references(&self) -> Vec<Entity<'tu>> {
+        let usr = self.get_usr();
+        let canonical = self.get_canonical_entity();
+
+        let mut references = vec![];
+        self.tu.get_entity().visit_children(|e, _| {
+            let kind = e.get_kind();
+            let expression = kind == EntityKind::DeclRefExpr || kind == EntityKind::MemberRefExpr;
+            if expression || e.is_reference() {
+                if let Some(reference) = e.get_reference() {
+                    let same = match usr {
+                        Some(ref usr) => reference.get_usr().as_ref() == Some(usr),
+                        None => reference.get_canonical_entity() == canonical,
+                    };
+
+                    if same {
+                        references.push(e);
+                    }
+                }
+            }
+            EntityVisitResult::Recurse
+        });
+        references
+    }
+
+    /// Returns the declarations in the translation unit whose semantic parent is this AST entity.
+    ///
+    /// Unlike `get_children`, which returns the lexical children of this AST entity, this includes
+    /// declarations which are lexically outside of this AST entity (e.g., the out-of-line
+    /// definition of a method is a semantic child of its class) and excludes declarations which
+    /// are lexically inside of this AST entity but semantically belong to another AST entity.
+    ///
+    /// Since `libclang` does not expose the semantic children of an AST entity, this visits every
+    /// AST entity in the translation unit and so takes time proportional to the size of the
+    /// translation unit. When the semantic children of many AST entities are needed, visit the
+    /// translation unit once and group the declarations by `get_semantic_parent` instead.
+    pub fn get_semantic_children(&self) -> Vec<Entity<'tu>> {
+        let mut children = vec![];
+        self.get_translation_unit().get_entity().visit_children(|c, _| {
+            if c.is_declaration() && c.get_semantic_parent() == Some(*self) {
+                children.push(c);
+            }
+            EntityVisitResult::Recurse
+        });
+        children
+    }
+
     /// Returns the semantic parent of this AST entity, if any.
     pub fn get_semantic_parent(&self) -> Option<Entity<'tu>> {
         let parent = unsafe { clang_getCursorSemanticParent(self.raw) };
         parent.map(|p| Entity::from_raw(p, self.tu))
     }
 
+    /// Returns the condition and message of this `static_assert` declaration, if applicable.
+    #[cfg(feature="clang_3_9")]
+    pub fn get_static_assert(&self) -> Option<StaticAssert<'tu>> {
+        if self.get_kind() != EntityKind::StaticAssert {
+            return None;
+        }
+
+        let children = self.get_children();
+        let condition = *children.first()?;
+        let message = children.get(1).and_then(|m| match m.evaluate() {
+            Some(EvaluationResult::String(string)) => string.into_string().ok(),
+            _ => None,
+        });
+        Some(StaticAssert { condition, message })
+    }
+
     /// Returns the storage class of this declaration, if applicable.
     #[cfg(feature="clang_3_6")]
     pub fn get_storage_class(&self) -> Option<StorageClass> {
@@ -2317,6 +3824,50 @@ impl<'tu> Entity<'tu> {
         unsafe { clang_getCursorType(self.raw).map(|t| Type::from_raw(t, self.tu)) }
     }
 
+    /// Returns the spelling of the type of this declaration as it was written in the source code
+    /// (e.g., `size_t` rather than `unsigned long`), if applicable.
+    ///
+    /// Unlike `get_type().get_display_name()`, this is faithful to the source code since it is
+    /// the text between the start of this declaration and its name, without any leading template
+    /// parameter list or specifiers such as `static` and `inline`. The parts of the declarator
+    /// which follow the name (e.g., `[3]` in `int a[3]`) are not included.
+    pub fn get_type_spelling_as_written(&self) -> Option<String> {
+        const SPECIFIERS: &[&str] = &[
+            "_Thread_local", "__thread", "consteval", "constexpr", "constinit", "explicit",
+            "extern", "friend", "inline", "mutable", "register", "static", "thread_local",
+            "typedef", "virtual",
+        ];
+
+        if !self.is_declaration() || self.get_type().is_none() {
+            return None;
+        }
+
+        let location = self.get_location()?;
+        let tokens = self.get_range()?.tokenize();
+        let end = tokens.iter().position(|t| t.get_location() == location)?;
+        let mut tokens = &tokens[..end];
+
+        if tokens.first().map_or(false, |t| t.get_spelling() == "template") {
+            let mut depth = 0;
+            let close = tokens.iter().position(|t| {
+                match &t.get_spelling()[..] {
+                    "<" => depth += 1,
+                    ">" => depth -= 1,
+                    ">>" => depth -= 2,
+                    _ => { },
+                }
+                depth <= 0 && t.get_spelling().starts_with('>')
+            })?;
+            tokens = &tokens[close + 1..];
+        }
+
+        let start = tokens.iter().position(|t| !SPECIFIERS.contains(&&t.get_spelling()[..]))?;
+        let tokens = &tokens[start..];
+        let end = tokens[tokens.len() - 1].get_range().get_end();
+        let text = SourceRange::new(tokens[0].get_location(), end).get_text()?;
+        Some(text.trim().into())
+    }
+
     /// Returns the underlying type of this typedef declaration, if applicable.
     pub fn get_typedef_underlying_type(&self) -> Option<Type<'tu>> {
         unsafe { clang_getTypedefDeclUnderlyingType(self.raw).map(|t| Type::from_raw(t, self.tu)) }
@@ -2327,6 +3878,180 @@ impl<'tu> Entity<'tu> {
         unsafe { utility::to_string_option(clang_getCursorUSR(self.raw)).map(Usr) }
     }
 
+    /// Returns the value category of this expression, if it can be determined.
+    ///
+    /// `libclang` does not expose value categories so this is a best-effort heuristic based on the
+    /// kind of this expression using the C++ rules. Operators and casts are distinguished by their
+    /// tokens and calls by the result type of the called function. `None` is returned for
+    /// expressions which are not handled (e.g., unexposed or conditional expressions).
+    pub fn get_value_category(&self) -> Option<ValueCategory> {
+        fn offset(location: SourceLocation) -> u32 {
+            location.get_file_location().offset
+        }
+
+        fn from_reference(token: Option<&Token>) -> ValueCategory {
+            match token.map(|t| t.get_spelling()).as_ref().map(|s| &s[..]) {
+                Some("&") => ValueCategory::LValue,
+                Some("&&") => ValueCategory::XValue,
+                _ => ValueCategory::PRValue,
+            }
+        }
+
+        fn from_type(type_: Type) -> ValueCategory {
+            match type_.get_kind() {
+                TypeKind::LValueReference => ValueCategory::LValue,
+                TypeKind::RValueReference => ValueCategory::XValue,
+                _ => ValueCategory::PRValue,
+            }
+        }
+
+        let category = match self.get_kind() {
+            EntityKind::DeclRefExpr | EntityKind::MemberRefExpr => {
+                match self.get_reference().map(|r| r.get_kind()) {
+                    Some(EntityKind::EnumConstantDecl) |
+                    Some(EntityKind::NonTypeTemplateParameter) => ValueCategory::PRValue,
+                    _ => ValueCategory::LValue,
+                }
+            },
+            EntityKind::ArraySubscriptExpr |
+            EntityKind::CompoundAssignOperator |
+            EntityKind::StringLiteral |
+            EntityKind::TypeidExpr => ValueCategory::LValue,
+            EntityKind::AddrLabelExpr |
+            EntityKind::BoolLiteralExpr |
+            EntityKind::CharacterLiteral |
+            EntityKind::DeleteExpr |
+            EntityKind::FloatingLiteral |
+            EntityKind::FunctionalCastExpr |
+            EntityKind::GNUNullExpr |
+            EntityKind::ImaginaryLiteral |
+            EntityKind::IntegerLiteral |
+            EntityKind::LambdaExpr |
+            EntityKind::NewExpr |
+            EntityKind::NullPtrLiteralExpr |
+            EntityKind::ObjCBoolLiteralExpr |
+            EntityKind::ObjCStringLiteral |
+            EntityKind::SizeOfPackExpr |
+            EntityKind::ThisExpr |
+            EntityKind::ThrowExpr |
+            EntityKind::UnaryExpr => ValueCategory::PRValue,
+            EntityKind::ParenExpr => return self.get_children().first()?.get_value_category(),
+            EntityKind::CallExpr => from_type(self.get_reference()?.get_result_type()?),
+            EntityKind::UnaryOperator => {
+                let operand = offset(self.get_children().first()?.get_range()?.get_start());
+                let tokens = self.get_range()?.tokenize();
+                match tokens.first() {
+                    Some(t) if offset(t.get_location()) < operand => {
+                        match &t.get_spelling()[..] {
+                            "*" | "++" | "--" => ValueCategory::LValue,
+                            _ => ValueCategory::PRValue,
+                        }
+                    },
+                    _ => ValueCategory::PRValue,
+                }
+            },
+            EntityKind::BinaryOperator => {
+                let children = self.get_children();
+                let lhs = offset(children.first()?.get_range()?.get_end());
+                let tokens = self.get_range()?.tokenize();
+                let operator = tokens.iter().find(|t| offset(t.get_location()) >= lhs)?;
+                match &operator.get_spelling()[..] {
+                    "," => return children.get(1)?.get_value_category(),
+                    "=" | "*=" | "/=" | "%=" | "+=" | "-=" | "<<=" | ">>=" | "&=" | "^=" | "|=" |
+                    ".*" | "->*" => ValueCategory::LValue,
+                    _ => ValueCategory::PRValue,
+                }
+            },
+            EntityKind::StaticCastExpr |
+            EntityKind::DynamicCastExpr |
+            EntityKind::ReinterpretCastExpr |
+            EntityKind::ConstCastExpr => {
+                let tokens = self.get_range()?.tokenize();
+                let index = tokens.iter().position(|t| t.get_spelling() == "(")?;
+                from_reference(tokens[..index].iter().rev().nth(1))
+            },
+            EntityKind::CStyleCastExpr => {
+                let tokens = self.get_range()?.tokenize();
+                let mut depth = 0;
+                let index = tokens.iter().position(|t| {
+                    match &t.get_spelling()[..] {
+                        "(" => depth += 1,
+                        ")" => depth -= 1,
+                        _ => { },
+                    }
+                    depth == 0
+                })?;
+                from_reference(tokens[..index].last())
+            },
+            _ => return None,
+        };
+
+        Some(category)
+    }
+
+    /// Returns the index of this virtual method in the virtual method table of its class, if
+    /// applicable.
+    ///
+    /// `libclang` does not expose virtual method tables so this is an approximation of the Itanium
+    /// C++ ABI layout. The table of a class starts with the table of its primary base class (its
+    /// first non-virtual base class with virtual methods), followed by the virtual methods declared
+    /// in the class which do not override a method in that table, in declaration order. Overriding
+    /// methods (matched by name, parameter types, and `const`) reuse the index of the method they
+    /// override and virtual destructors occupy two consecutive indices. The offset-to-top and RTTI
+    /// entries which precede the virtual methods in the table are not counted.
+    pub fn get_virtual_method_index(&self) -> Option<usize> {
+        fn get_key(method: &Entity) -> Option<String> {
+            if method.get_kind() == EntityKind::Destructor {
+                Some("~".into())
+            } else {
+                let constness = if method.is_const_method() { " const" } else { "" };
+                method.get_display_name().map(|n| format!("{}{}", n, constness))
+            }
+        }
+
+        fn get_table(class: &Entity) -> Vec<String> {
+            let children = class.get_children();
+
+            let mut table = children.iter().filter(|c| {
+                c.get_kind() == EntityKind::BaseSpecifier && !c.is_virtual_base()
+            }).filter_map(|b| {
+                let base = b.get_type()?.get_canonical_type().get_declaration()?;
+                let base = base.get_definition()?;
+                Some(get_table(&base))
+            }).find(|t| !t.is_empty()).unwrap_or_default();
+
+            for child in &children {
+                let kind = child.get_kind();
+                if (kind != EntityKind::Method && kind != EntityKind::Destructor) ||
+                   !child.is_virtual_method() {
+                    continue;
+                }
+
+                let key = match get_key(child) {
+                    Some(key) => key,
+                    None => continue,
+                };
+
+                if !table.contains(&key) {
+                    if kind == EntityKind::Destructor {
+                        table.push(key.clone());
+                    }
+                    table.push(key);
+                }
+            }
+            table
+        }
+
+        if !self.is_virtual_method() {
+            return None;
+        }
+
+        let key = get_key(self)?;
+        let class = self.get_semantic_parent()?;
+        let class = class.get_definition().unwrap_or(class);
+        get_table(&class).iter().position(|k| *k == key)
+    }
+
     /// Returns the linker visibility for this AST entity, if any.
     #[cfg(feature="clang_3_8")]
     pub fn get_visibility(&self) -> Option<Visibility> {
@@ -2349,6 +4074,24 @@ impl<'tu> Entity<'tu> {
         unsafe { clang_Cursor_hasAttrs(self.raw) != 0 }
     }
 
+    /// Returns whether the definition of this declaration is present in the translation unit.
+    ///
+    /// Unlike `is_definition`, which indicates whether this particular declaration is the
+    /// definition, this indicates whether any declaration of this entity in the translation unit is
+    /// the definition (e.g., `false` for an `extern` variable that is defined elsewhere).
+    pub fn has_definition_in_tu(&self) -> bool {
+        let location = self.get_definition().and_then(|d| d.get_location());
+        location.map_or(false, |l| l.get_file().is_some())
+    }
+
+    /// Returns whether this C++ record has a destructor which is neither implicit nor defaulted.
+    #[cfg(feature="clang_3_9")]
+    pub fn has_user_declared_destructor(&self) -> bool {
+        self.get_children().iter().any(|c| {
+            c.get_kind() == EntityKind::Destructor && !c.is_defaulted() && !c.is_implicit()
+        })
+    }
+
     /// Returns whether this AST entity is an abstract C++ record.
     #[cfg(feature="clang_6_0")]
     pub fn is_abstract_record(&self) -> bool {
@@ -2382,6 +4125,31 @@ impl<'tu> Entity<'tu> {
         unsafe { clang_Cursor_isBitField(self.raw) != 0 }
     }
 
+    /// Returns whether this AST entity is a declaration or macro definition provided by the
+    /// compiler (e.g., `__builtin_va_list`).
+    ///
+    /// `libclang` does not directly expose whether a declaration is a builtin. Instead, this is
+    /// determined by checking whether this AST entity has a name that starts with `__builtin` or a
+    /// location that is not in a file and is either invalid or in the `<built-in>` buffer.
+    pub fn is_builtin(&self) -> bool {
+        if !self.is_declaration() && !self.is_preprocessing() {
+            return false;
+        }
+
+        if self.get_name().map_or(false, |n| n.starts_with("__builtin")) {
+            return true;
+        }
+
+        match self.get_location() {
+            Some(location) if location.get_file_location().file.is_none() => {
+                let (file, _, _) = location.get_presumed_location();
+                file.is_empty() || file == "<built-in>"
+            },
+            Some(_) => false,
+            None => true,
+        }
+    }
+
     /// Returns whether this AST entity is a builtin macro.
     #[cfg(feature="clang_3_9")]
     pub fn is_builtin_macro(&self) -> bool {
@@ -2393,6 +4161,13 @@ impl<'tu> Entity<'tu> {
         unsafe { clang_CXXMethod_isConst(self.raw) != 0 }
     }
 
+    /// Returns whether this declaration has the `constexpr`, `consteval`, or `constinit` specifier.
+    ///
+    /// See `get_constexpr_kind` for how these specifiers are found.
+    pub fn is_constexpr(&self) -> bool {
+        self.get_constexpr_kind().is_some()
+    }
+
     /// Returns whether this AST entity is a C++ converting constructor.
     #[cfg(feature="clang_3_9")]
     pub fn is_converting_constructor(&self) -> bool {
@@ -2431,12 +4206,71 @@ impl<'tu> Entity<'tu> {
         unsafe { clang_Cursor_isDynamicCall(self.raw) != 0 }
     }
 
+    /// Returns whether this AST entity is a function template parameter which is a forwarding
+    /// reference (e.g., `t` in `template <class T> void f(T&& t);`).
+    ///
+    /// This is a heuristic. A parameter is considered a forwarding reference if its type is an
+    /// rvalue reference to an unqualified type which has the same name as a template type
+    /// parameter of the function template that declares the parameter.
+    pub fn is_forwarding_reference_parameter(&self) -> bool {
+        if self.get_kind() != EntityKind::ParmDecl {
+            return false;
+        }
+
+        let type_ = match self.get_type() {
+            Some(type_) if type_.get_kind() == TypeKind::RValueReference => type_,
+            _ => return false,
+        };
+
+        let referent = match type_.get_pointee_type() {
+            Some(referent) => referent,
+            None => return false,
+        };
+
+        if referent.is_const_qualified() || referent.is_volatile_qualified() {
+            return false;
+        }
+
+        let parent = match self.get_semantic_parent() {
+            Some(parent) if parent.get_kind() == EntityKind::FunctionTemplate => parent,
+            _ => return false,
+        };
+
+        let name = referent.get_display_name();
+        parent.get_children().iter().any(|c| {
+            let kind = c.get_kind();
+            kind == EntityKind::TemplateTypeParameter && c.get_name().as_ref() == Some(&name)
+        })
+    }
+
     /// Returns whether this AST entity is a function-like macro.
     #[cfg(feature="clang_3_9")]
     pub fn is_function_like_macro(&self) -> bool {
         unsafe { clang_Cursor_isMacroFunctionLike(self.raw) != 0 }
     }
 
+    /// Returns whether this AST entity is a declaration that was implicitly generated by the
+    /// compiler (e.g., an implicit default constructor).
+    ///
+    /// `libclang` does not expose whether a declaration is implicit (the indexing API skips most
+    /// implicit declarations) so this is a best-effort heuristic. A declaration is
+    /// considered implicit if it has no source location or has the same source location as its
+    /// semantic parent. This holds for the implicit declarations generated by Clang for C++
+    /// classes (e.g., implicit special members and the injected class name of a record), but it
+    /// is not guaranteed and may give the wrong answer for other declarations.
+    pub fn is_implicit(&self) -> bool {
+        if !self.is_declaration() {
+            return false;
+        }
+
+        match self.get_location() {
+            Some(location) => {
+                self.get_semantic_parent().and_then(|p| p.get_location()) == Some(location)
+            },
+            None => true,
+        }
+    }
+
     /// Returns whether this AST entity is an inline function.
     #[cfg(feature="clang_3_9")]
     pub fn is_inline_function(&self) -> bool {
@@ -2461,6 +4295,37 @@ impl<'tu> Entity<'tu> {
         unsafe { clang_CXXField_isMutable(self.raw) != 0 }
     }
 
+    /// Returns whether this AST entity is a function or method that does not return (e.g., one
+    /// declared with `_Noreturn`, `[[noreturn]]`, or `__attribute__((noreturn))`).
+    ///
+    /// `libclang` does not expose `noreturn` attributes as a distinct kind of AST entity. Instead,
+    /// this is determined by checking the tokens of the attributes attached to this AST entity and
+    /// the tokens which precede the name of this AST entity for a `noreturn` spelling.
+    pub fn is_noreturn(&self) -> bool {
+        match self.get_kind() {
+            EntityKind::FunctionDecl | EntityKind::Method | EntityKind::FunctionTemplate => { },
+            _ => return false,
+        }
+
+        let noreturn = |t: &Token| {
+            matches!(&t.get_spelling()[..], "noreturn" | "__noreturn__" | "_Noreturn")
+        };
+
+        let attribute = self.get_children().iter().any(|c| {
+            c.is_attribute() && c.get_range().map_or(false, |r| r.tokenize().iter().any(noreturn))
+        });
+
+        attribute || match (self.get_range(), self.get_location()) {
+            (Some(range), Some(location)) => {
+                let name = location.get_spelling_location().offset;
+                range.tokenize().iter().take_while(|t| {
+                    t.get_location().get_spelling_location().offset < name
+                }).any(noreturn)
+            },
+            _ => false,
+        }
+    }
+
     /// Returns whether this AST entity is an Objective-C method or property declaration with the
     /// `@optional` attribute applied to it.
     pub fn is_objc_optional(&self) -> bool {
@@ -2472,6 +4337,24 @@ impl<'tu> Entity<'tu> {
         unsafe { clang_CXXMethod_isPureVirtual(self.raw) != 0 }
     }
 
+    /// Returns whether this AST entity is a scoped enum.
+    ///
+    /// Unlike `is_scoped`, this method does not require `libclang` 5.0 or later since it inspects
+    /// the tokens of the enum declaration instead (e.g., `enum class` or `enum struct`).
+    pub fn enum_is_scoped(&self) -> bool {
+        if self.get_kind() != EntityKind::EnumDecl {
+            return false;
+        }
+
+        let tokens = self.get_range().map(|r| r.tokenize()).unwrap_or_default();
+        let mut spellings = tokens.iter().map(|t| t.get_spelling());
+        if spellings.position(|s| s == "enum").is_none() {
+            return false;
+        }
+
+        spellings.next().map_or(false, |s| s == "class" || s == "struct")
+    }
+
     /// Returns whether this AST entity is a scoped enum.
     #[cfg(feature="clang_5_0")]
     pub fn is_scoped(&self) -> bool {
@@ -2498,6 +4381,14 @@ impl<'tu> Entity<'tu> {
         unsafe { clang_CXXMethod_isVirtual(self.raw) != 0 }
     }
 
+    /// Returns the AST entity referred to by this reference (e.g., a `DeclRefExpr` or
+    /// `MemberRefExpr`) and the type of this reference, if this AST entity refers to another.
+    ///
+    /// This is equivalent to calling both `get_reference` and `get_type`.
+    pub fn resolve_reference(&self) -> Option<(Entity<'tu>, Option<Type<'tu>>)> {
+        self.get_reference().map(|r| (r, self.get_type()))
+    }
+
     /// Visits the children of this AST entity recursively and returns whether visitation was ended
     /// by the callback returning `EntityVisitResult::Break`.
     ///
@@ -2535,6 +4426,21 @@ impl<'tu> Entity<'tu> {
         unsafe { clang_visitChildren(self.raw, visit, utility::addressof(&mut data)) != 0 }
     }
 
+    /// Visits the descendants of this AST entity, calling the supplied function on each one and
+    /// only recursing into those descendants for which the supplied predicate returns `true`.
+    pub fn visit_children_bounded<P: Fn(&Entity<'tu>) -> bool, F: FnMut(&Entity<'tu>)>(
+        &self, should_recurse: P, mut f: F
+    ) {
+        self.visit_children(|e, _| {
+            f(&e);
+            if should_recurse(&e) {
+                EntityVisitResult::Recurse
+            } else {
+                EntityVisitResult::Continue
+            }
+        });
+    }
+
     //- Categorization ---------------------------
 
     /// Returns whether this AST entity is categorized as an attribute.
@@ -2579,6 +4485,19 @@ impl<'tu> Entity<'tu> {
         self.get_range().map_or(false, |r| r.is_in_main_file())
     }
 
+    /// Returns whether this AST entity is in the same file as the supplied AST entity.
+    ///
+    /// The files are compared by their unique IDs. `false` will be returned if either AST entity
+    /// is not in a file.
+    pub fn is_in_same_file_as(&self, other: &Entity) -> bool {
+        let a = self.get_location().and_then(|l| l.get_file_location().file);
+        let b = other.get_location().and_then(|l| l.get_file_location().file);
+        match (a, b) {
+            (Some(a), Some(b)) => a.get_id() == b.get_id(),
+            _ => false,
+        }
+    }
+
     /// Returns whether this AST entity is in a system header.
     pub fn is_in_system_header(&self) -> bool {
         self.get_range().map_or(false, |r| r.is_in_system_header())
@@ -2614,6 +4533,34 @@ impl<'tu> hash::Hash for Entity<'tu> {
     }
 }
 
+// EnumConstant __________________________________
+
+/// The name and value of an enum constant.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EnumConstant {
+    /// The name of the enum constant.
+    pub name: String,
+    /// The value of the enum constant as a signed integer.
+    pub signed: i64,
+    /// The value of the enum constant as an unsigned integer.
+    pub unsigned: u64,
+}
+
+// EnumInfo ______________________________________
+
+/// The name, scopedness, underlying type, and constants of an enum declaration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnumInfo<'tu> {
+    /// The name of the enum, if any.
+    pub name: Option<String>,
+    /// Whether the enum is a scoped enum (e.g., `enum class`).
+    pub is_scoped: bool,
+    /// The underlying type of the enum.
+    pub underlying_type: Type<'tu>,
+    /// The names and values of the constants in the enum.
+    pub constants: Vec<EnumConstant>,
+}
+
 // ExternalSymbol ________________________________
 
 /// An `external_source_symbol` attribute.
@@ -2628,11 +4575,63 @@ pub struct ExternalSymbol {
     pub generated: bool,
 }
 
+// FieldLayout ___________________________________
+
+/// The layout of a field in a record type.
+#[cfg(feature="clang_3_7")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldLayout<'tu> {
+    /// The name of the field, if any.
+    pub name: Option<String>,
+    /// The type of the field.
+    pub type_: Type<'tu>,
+    /// The offset of the field in bits.
+    pub offset_bits: usize,
+    /// The size of the field in bits (the width of the field if it is a bit field).
+    pub size_bits: usize,
+    /// The alignment of the type of the field in bits.
+    pub align_bits: usize,
+}
+
+// FormatAttribute _______________________________
+
+/// A `format` attribute (e.g., `__attribute__((format(printf, 1, 2)))`).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FormatAttribute {
+    /// The format string archetype (e.g., `printf` or `scanf`).
+    pub archetype: String,
+    /// The one-based index of the format string parameter.
+    pub format_index: usize,
+    /// The one-based index of the first parameter to check against the format string or `0` if
+    /// the parameters can't be checked (e.g., for `vprintf`-like functions).
+    pub first_to_check: usize,
+}
+
+// FunctionTypeInfo ______________________________
+
+/// The result type, argument types, variadicness, calling convention, and exception specification
+/// of a function prototype type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionTypeInfo<'tu> {
+    /// The result type of the function type.
+    pub result_type: Type<'tu>,
+    /// The argument types of the function type.
+    pub argument_types: Vec<Type<'tu>>,
+    /// Whether the function type is variadic.
+    pub variadic: bool,
+    /// The calling convention of the function type, if any.
+    pub calling_convention: Option<CallingConvention>,
+    /// The exception specification of the function type, if any.
+    #[cfg(feature="clang_5_0")]
+    pub exception_specification: Option<ExceptionSpecification>,
+}
+
 // Index _________________________________________
 
 /// A collection of translation units.
 pub struct Index<'c> {
     ptr: CXIndex,
+    options: IndexOptions,
     _marker: PhantomData<&'c Clang>,
 }
 
@@ -2641,7 +4640,7 @@ impl<'c> Index<'c> {
 
     fn from_ptr(ptr: CXIndex) -> Index<'c> {
         assert!(!ptr.is_null());
-        Index { ptr, _marker: PhantomData }
+        Index { ptr, options: IndexOptions::default(), _marker: PhantomData }
     }
 
     /// Constructs a new `Index`.
@@ -2652,6 +4651,16 @@ impl<'c> Index<'c> {
         unsafe { Index::from_ptr(clang_createIndex(exclude as c_int, diagnostics as c_int)) }
     }
 
+    /// Constructs a new `Index` with the supplied indexing options.
+    ///
+    /// Declarations from precompiled headers are not excluded and diagnostics are not printed
+    /// while parsing source files.
+    pub fn with_options(clang: &'c Clang, options: IndexOptions) -> Index<'c> {
+        let mut index = Index::new(clang, false, false);
+        index.options = options;
+        index
+    }
+
     //- Accessors --------------------------------
 
     /// Returns a parser for the supplied file.
@@ -2659,6 +4668,72 @@ impl<'c> Index<'c> {
         Parser::new(self, f)
     }
 
+    /// Indexes the supplied translation unit using the indexing options for this index and returns
+    /// whether indexing succeeded.
+    ///
+    /// The callback is called for each declaration and reference found while indexing. For
+    /// declarations the first argument is the declared AST entity and the second argument is
+    /// `None`. For references the first argument is the referring AST entity and the second
+    /// argument is the referenced AST entity.
+    pub fn index_translation_unit<'tu, F: FnMut(Entity<'tu>, Option<Entity<'tu>>)>(
+        &self, tu: &'tu TranslationUnit<'tu>, mut f: F
+    ) -> bool {
+        trait IndexCallback<'tu> {
+            fn call(&mut self, entity: Entity<'tu>, referenced: Option<Entity<'tu>>);
+        }
+
+        impl<'tu, F: FnMut(Entity<'tu>, Option<Entity<'tu>>)> IndexCallback<'tu> for F {
+            fn call(&mut self, entity: Entity<'tu>, referenced: Option<Entity<'tu>>) {
+                self(entity, referenced)
+            }
+        }
+
+        extern fn declaration(data: CXClientData, info: *const CXIdxDeclInfo) {
+            unsafe {
+                let &mut (tu, ref mut callback) =
+                    &mut *(data as *mut (&TranslationUnit, &mut dyn IndexCallback));
+
+                if let Some(info) = info.as_ref() {
+                    callback.call(Entity::from_raw(info.cursor, tu), None);
+                }
+            }
+        }
+
+        extern fn reference(data: CXClientData, info: *const CXIdxEntityRefInfo) {
+            unsafe {
+                let &mut (tu, ref mut callback) =
+                    &mut *(data as *mut (&TranslationUnit, &mut dyn IndexCallback));
+
+                if let Some(info) = info.as_ref() {
+                    let referenced = info.referencedEntity.as_ref();
+                    let referenced = referenced.map(|e| Entity::from_raw(e.cursor, tu));
+                    callback.call(Entity::from_raw(info.cursor, tu), referenced);
+                }
+            }
+        }
+
+        let mut callbacks = IndexerCallbacks {
+            indexDeclaration: Some(declaration),
+            indexEntityReference: Some(reference),
+            .. IndexerCallbacks::default()
+        };
+
+        let mut data = (tu, &mut f as &mut dyn IndexCallback);
+        unsafe {
+            let action = clang_IndexAction_create(self.ptr);
+            let code = clang_indexTranslationUnit(
+                action,
+                utility::addressof(&mut data),
+                &mut callbacks,
+                mem::size_of::<IndexerCallbacks>() as c_uint,
+                self.options.into(),
+                tu.ptr,
+            );
+            clang_IndexAction_dispose(action);
+            code == 0
+        }
+    }
+
     /// Sets the invocation emission path for this index.
     #[cfg(feature="clang_6_0")]
     pub fn set_invocation_emission_path<P: AsRef<Path>>(&'c self, path: P) {
@@ -2671,8 +4746,18 @@ impl<'c> Index<'c> {
         unsafe { ThreadOptions::from(clang_CXIndex_getGlobalOptions(self.ptr)) }
     }
 
+    /// Returns the indexing options for this index.
+    pub fn get_index_options(&self) -> IndexOptions {
+        self.options
+    }
+
     //- Mutators ---------------------------------
 
+    /// Sets the indexing options for this index.
+    pub fn set_index_options(&mut self, options: IndexOptions) {
+        self.options = options;
+    }
+
     /// Sets the thread options for this index.
     pub fn set_thread_options(&mut self, options: ThreadOptions) {
         unsafe { clang_CXIndex_setGlobalOptions(self.ptr, options.into()); }
@@ -2688,11 +4773,32 @@ impl<'c> Drop for Index<'c> {
 impl<'c> fmt::Debug for Index<'c> {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         formatter.debug_struct("Index")
+            .field("index_options", &self.get_index_options())
             .field("thread_options", &self.get_thread_options())
             .finish()
     }
 }
 
+// IndexOptions __________________________________
+
+options! {
+    /// A set of options that determines how source files are indexed.
+    #[derive(Default)]
+    options IndexOptions: CXIndexOptFlags {
+        /// Indicates whether references to an entity which are redundant should be suppressed.
+        pub suppress_redundant_references: CXIndexOptSuppressRedundantRefs,
+        /// Indicates whether symbols which are local to a function should be indexed.
+        pub index_function_local_symbols: CXIndexOptIndexFunctionLocalSymbols,
+        /// Indicates whether implicit template instantiations should be indexed.
+        pub index_implicit_template_instantiations: CXIndexOptIndexImplicitTemplateInstantiations,
+        /// Indicates whether warnings should be suppressed.
+        pub suppress_warnings: CXIndexOptSuppressWarnings,
+        /// Indicates whether function bodies which have already been parsed in the same indexing
+        /// session should be skipped.
+        pub skip_parsed_bodies_in_session: CXIndexOptSkipParsedBodiesInSession,
+    }
+}
+
 // ObjCAttributes ________________________________
 
 options! {
@@ -2728,6 +4834,20 @@ options! {
     }
 }
 
+// ObjCObjectInfo ________________________________
+
+/// The base type, type arguments, and protocols of an Objective-C object type.
+#[cfg(feature="clang_8_0")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjCObjectInfo<'tu> {
+    /// The base type of the Objective-C object type.
+    pub base_type: Type<'tu>,
+    /// The type arguments of the Objective-C object type.
+    pub type_arguments: Vec<Type<'tu>>,
+    /// The declarations of the protocols the Objective-C object type is qualified with.
+    pub protocols: Vec<Entity<'tu>>,
+}
+
 // ObjCQualifiers ________________________________
 
 options! {
@@ -2748,6 +4868,47 @@ options! {
     }
 }
 
+// OwnedTranslationUnit __________________________
+
+/// A translation unit which owns the index it was parsed with.
+///
+/// See `parse_cpp`.
+pub struct OwnedTranslationUnit<'c> {
+    // The translation unit must be dropped before the index it was parsed with.
+    tu: TranslationUnit<'static>,
+    _index: Index<'c>,
+}
+
+impl<'c> OwnedTranslationUnit<'c> {
+    //- Accessors --------------------------------
+
+    /// Returns the translation unit owned by this owned translation unit.
+    pub fn get<'a>(&'a self) -> &'a TranslationUnit<'a> {
+        &self.tu
+    }
+}
+
+impl<'c> fmt::Debug for OwnedTranslationUnit<'c> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_struct("OwnedTranslationUnit").field("tu", &self.tu).finish()
+    }
+}
+
+// Parameter _____________________________________
+
+/// A parameter of a function or method declaration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Parameter<'tu> {
+    /// The name of the parameter, if any.
+    pub name: Option<String>,
+    /// The type of the parameter.
+    pub type_: Type<'tu>,
+    /// The default argument of the parameter, if any.
+    pub default_argument: Option<Entity<'tu>>,
+    /// The index of the parameter.
+    pub index: usize,
+}
+
 // Parser ________________________________________
 
 builder! {
@@ -2826,12 +4987,63 @@ impl<'tu> Parser<'tu> {
         self
     }
 
+    /// Sets a header to be included before the source file by appending `-include <header>` to
+    /// the compiler arguments.
+    ///
+    /// Note that any subsequent calls to `arguments` will replace these arguments.
+    pub fn force_include(&mut self, header: &Path) -> &mut Parser<'tu> {
+        self.arguments.push(utility::from_string("-include"));
+        self.arguments.push(utility::from_path(header));
+        self
+    }
+
+    /// Enables including attributed types and visiting implicit attributes.
+    ///
+    /// This is a shorthand for enabling both `include_attributed_types` and
+    /// `visit_implicit_attributes`, which are only supported by `libclang` 8.0 and later.
+    #[cfg(feature="clang_8_0")]
+    pub fn full_attribute_info(&mut self) -> &mut Parser<'tu> {
+        self.include_attributed_types(true).visit_implicit_attributes(true)
+    }
+
+    /// Sets whether comments in system headers will be retained by appending
+    /// `-fretain-comments-from-system-headers` to the compiler arguments.
+    ///
+    /// Without this, `Entity::get_comment` and related methods will return `None` for declarations
+    /// in system headers. Note that any subsequent calls to `arguments` will replace these
+    /// arguments.
+    pub fn retain_comments_from_system_headers(&mut self) -> &mut Parser<'tu> {
+        self.arguments.push(utility::from_string("-fretain-comments-from-system-headers"));
+        self
+    }
+
+    /// Sets the target triple to parse for by appending `-target <triple>` to the compiler
+    /// arguments (e.g., `aarch64-apple-darwin`).
+    ///
+    /// Note that any subsequent calls to `arguments` will replace these arguments.
+    pub fn target(&mut self, triple: &str) -> &mut Parser<'tu> {
+        self.arguments.push(utility::from_string("-target"));
+        self.arguments.push(utility::from_string(triple));
+        self
+    }
+
     /// Sets the unsaved files to use.
     pub fn unsaved(&mut self, unsaved: &[Unsaved]) -> &mut Parser<'tu> {
         self.unsaved = unsaved.into();
         self
     }
 
+    /// Sets the options recommended by `libclang` for translation units which will be reparsed and
+    /// used for code completion (e.g., in an editor).
+    ///
+    /// These options are set in addition to any options already set. On current versions of
+    /// `libclang` this sets `cache_completion_results` and the option which causes a precompiled
+    /// preamble to be created (which speeds up reparsing).
+    pub fn with_editing_defaults(&mut self) -> &mut Parser<'tu> {
+        self.flags |= unsafe { clang_defaultEditingTranslationUnitOptions() };
+        self
+    }
+
     //- Accessors --------------------------------
 
     /// Parses a translation unit.
@@ -2895,6 +5107,17 @@ impl PlatformAvailability {
     }
 }
 
+// Pragma ________________________________________
+
+/// A `#pragma` directive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pragma<'tu> {
+    /// The text of the pragma directive following `pragma` (e.g., `pack(1)`).
+    pub text: String,
+    /// The source range of the pragma directive (from `#` through the last token).
+    pub range: SourceRange<'tu>,
+}
+
 // PrettyPrinter _________________________________
 
 /// Pretty prints declarations.
@@ -2953,6 +5176,119 @@ impl<'e> Drop for PrettyPrinter<'e> {
     }
 }
 
+// QualifiedNameCache ____________________________
+
+/// A cache of the qualified names of AST entities.
+///
+/// Qualified names are cached by USR so the names of the namespaces and records that contain many
+/// AST entities only need to be computed once. AST entities without a USR are not cached.
+#[derive(Clone, Debug, Default)]
+pub struct QualifiedNameCache {
+    names: HashMap<Usr, String>,
+}
+
+impl QualifiedNameCache {
+    //- Constructors -----------------------------
+
+    /// Constructs a new `QualifiedNameCache`.
+    pub fn new() -> QualifiedNameCache {
+        QualifiedNameCache::default()
+    }
+
+    //- Accessors --------------------------------
+
+    /// Returns the qualified name of the supplied AST entity (see `Entity::get_qualified_name`).
+    pub fn name_of(&mut self, entity: &Entity) -> String {
+        if entity.get_kind() == EntityKind::TranslationUnit {
+            return String::new();
+        }
+
+        let usr = entity.get_usr();
+        if let Some(name) = usr.as_ref().and_then(|u| self.names.get(u)) {
+            return name.clone();
+        }
+
+        let prefix = entity.get_semantic_parent().map(|p| self.name_of(&p)).unwrap_or_default();
+        let name = match entity.get_name() {
+            Some(name) if prefix.is_empty() => name,
+            Some(name) => format!("{}::{}", prefix, name),
+            None => prefix,
+        };
+
+        if let Some(usr) = usr {
+            self.names.insert(usr, name.clone());
+        }
+
+        name
+    }
+}
+
+// StaticAssert __________________________________
+
+/// The condition and message of a `static_assert` declaration.
+#[cfg(feature="clang_3_9")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StaticAssert<'tu> {
+    /// The condition of the `static_assert` declaration.
+    pub condition: Entity<'tu>,
+    /// The message of the `static_assert` declaration, if any.
+    pub message: Option<String>,
+}
+
+// SymbolTable ___________________________________
+
+/// A table of the declarations in a translation unit keyed by their USRs.
+///
+/// Each symbol is included once regardless of how many times it is declared. The included AST
+/// entity for each symbol is its definition if it is defined in the translation unit and its first
+/// declaration otherwise. Declarations without a USR are not included.
+#[derive(Clone, Debug)]
+pub struct SymbolTable<'tu> {
+    symbols: HashMap<Usr, Entity<'tu>>,
+}
+
+impl<'tu> SymbolTable<'tu> {
+    //- Constructors -----------------------------
+
+    /// Constructs a new `SymbolTable` from the declarations in the supplied translation unit.
+    pub fn new(tu: &'tu TranslationUnit<'tu>) -> SymbolTable<'tu> {
+        let mut symbols: HashMap<Usr, Entity<'tu>> = HashMap::new();
+        tu.get_entity().visit_children(|e, _| {
+            if let Some(usr) = e.get_usr().filter(|_| e.is_declaration()) {
+                let symbol = symbols.entry(usr).or_insert(e);
+                if e.is_definition() && !symbol.is_definition() {
+                    *symbol = e;
+                }
+            }
+            EntityVisitResult::Recurse
+        });
+        SymbolTable { symbols }
+    }
+
+    //- Accessors --------------------------------
+
+    /// Returns the AST entity for the symbol with the supplied USR, if any.
+    pub fn lookup(&self, usr: &Usr) -> Option<Entity<'tu>> {
+        self.symbols.get(usr).cloned()
+    }
+
+    /// Returns the number of symbols in this symbol table.
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Returns whether this symbol table contains no symbols.
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Returns an iterator over the USRs and AST entities of the symbols in this symbol table in an
+    /// arbitrary order.
+    pub fn iter<'s>(&'s self) -> hash_map::Iter<'s, Usr, Entity<'tu>> {
+        self.symbols.iter()
+    }
+}
+
 // Target ________________________________________
 
 /// Information about the target for a translation unit.
@@ -2994,6 +5330,16 @@ options! {
     }
 }
 
+impl ThreadOptions {
+    //- Constructors -----------------------------
+
+    /// Constructs a new `ThreadOptions` which indicates that all threads should use background
+    /// priority.
+    pub fn all() -> ThreadOptions {
+        ThreadOptions::from(CXGlobalOpt_ThreadBackgroundPriorityForAll)
+    }
+}
+
 // TranslationUnit _______________________________
 
 /// A preprocessed and parsed source file.
@@ -3023,8 +5369,50 @@ impl<'i> TranslationUnit<'i> {
         ptr.map(TranslationUnit::from_ptr).ok_or(())
     }
 
+    /// Constructs a new `TranslationUnit` from the contents of an AST file.
+    ///
+    /// `libclang` can only load AST files from disk so the supplied contents are written to a
+    /// temporary file which is loaded and then removed.
+    ///
+    /// # Failures
+    ///
+    /// * the temporary file could not be written
+    /// * an unknown error occurs
+    pub fn from_ast_bytes(index: &'i Index, bytes: &[u8]) -> Result<TranslationUnit<'i>, ()> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let count = COUNTER.fetch_add(1, atomic::Ordering::SeqCst);
+        let name = format!("clang-rs-{}-{}.ast", process::id(), count);
+        let file = env::temp_dir().join(name);
+
+        fs::write(&file, bytes).map_err(|_| ())?;
+        let tu = TranslationUnit::from_ast(index, &file);
+        let _ = fs::remove_file(&file);
+        tu
+    }
+
     //- Accessors --------------------------------
 
+    /// Returns the paths to the files directly or transitively included by this translation unit.
+    ///
+    /// Each file is only included once and the main file is not included. Files that are system
+    /// headers are only included if `system` is `true`.
+    pub fn get_dependencies(&'i self, system: bool) -> Vec<PathBuf> {
+        let mut files = vec![];
+        let mut seen = HashSet::new();
+        self.visit_inclusions(|f, stack| {
+            // The main file is the only file with an empty inclusion stack.
+            if stack.is_empty() {
+                return;
+            }
+
+            if (system || !f.get_offset_location(0).is_in_system_header()) && seen.insert(f) {
+                files.push(f.get_path());
+            }
+        });
+        files
+    }
+
     /// Returns the diagnostics for this translation unit.
     pub fn get_diagnostics(&'i self) -> Vec<Diagnostic<'i>> {
         iter!(clang_getNumDiagnostics(self.ptr), clang_getDiagnostic(self.ptr),).map(|d| {
@@ -3032,6 +5420,188 @@ impl<'i> TranslationUnit<'i> {
         }).collect()
     }
 
+    /// Returns the diagnostics for this translation unit which are located in its preamble.
+    ///
+    /// The preamble of a translation unit consists of the preprocessing directives at the start of
+    /// its main file (e.g., `#include` directives) and may be precompiled to speed up reparsing.
+    /// `libclang` does not distinguish the diagnostics produced while building a precompiled
+    /// preamble from the diagnostics produced while parsing the rest of the main file (both are
+    /// returned by `get_diagnostics`), so the diagnostics are instead filtered by whether their
+    /// locations are in the preamble. A diagnostic is in the preamble if it is located before the
+    /// first token of the main file which is not part of a leading preprocessing directive or if it
+    /// is located in a file included by such a directive.
+    pub fn get_preamble_diagnostics(&'i self) -> Vec<Diagnostic<'i>> {
+        let mut main = None;
+        let mut offsets = HashMap::new();
+        self.visit_inclusions(|f, stack| {
+            // The last location in the inclusion stack is the location in the main file.
+            match stack.last() {
+                Some(location) => { offsets.insert(f, location.get_file_location().offset); },
+                None => main = Some(f),
+            }
+        });
+
+        let main = match main {
+            Some(main) => main,
+            None => return vec![],
+        };
+
+        let end = get_preamble_end(main);
+
+        self.get_diagnostics().into_iter().filter(|d| {
+            let location = d.get_location().get_file_location();
+            match location.file {
+                Some(file) if file == main => location.offset < end,
+                Some(file) => offsets.get(&file).map_or(false, |&o| o < end),
+                None => false,
+            }
+        }).collect()
+    }
+
+    /// Returns the ranges of the conditional blocks in this translation unit which were excluded by
+    /// the preprocessor but retained because this translation unit was constructed with the
+    /// `retain_excluded_conditional_blocks` option (e.g., `#if 0` blocks).
+    ///
+    /// `libclang` does not expose which conditional blocks were excluded when they are retained so
+    /// only conditional blocks which are excluded regardless of any macros are returned. These are
+    /// the blocks with integer literal conditions which are zero (e.g., `#if 0`) and the blocks
+    /// which follow a block with an integer literal condition which is non-zero (e.g., the `#else`
+    /// block of `#if 1`). Conditional blocks excluded because of the values of macros (e.g., the
+    /// `#ifdef A` block when `A` is not defined) are not returned.
+    ///
+    /// Conditional blocks which were skipped rather than retained (i.e., those which start inside
+    /// a source range returned by `get_skipped_ranges`) are not returned, so the translation unit
+    /// must be constructed with a detailed preprocessing record. Excluded blocks nested in excluded
+    /// blocks are not returned separately.
+    #[cfg(feature="clang_10_0")]
+    pub fn get_excluded_blocks(&'i self) -> Vec<SourceRange<'i>> {
+        struct Conditional<'i> {
+            start: Option<SourceLocation<'i>>,
+            taken: Option<bool>,
+            ignored: bool,
+        }
+
+        fn evaluate(directive: &[Token]) -> Option<bool> {
+            match &directive[1..] {
+                [_, literal] if literal.get_kind() == TokenKind::Literal => {
+                    let literal = literal.get_spelling();
+                    let literal = literal.trim_end_matches(|c| "uUlL".contains(c));
+                    literal.parse::<u64>().ok().map(|l| l != 0)
+                },
+                _ => None,
+            }
+        }
+
+        let skipped = self.get_skipped_ranges().into_iter().map(|r| {
+            let start = r.get_start().get_file_location();
+            (start.file, start.offset, r.get_end().get_file_location().offset)
+        }).collect::<Vec<_>>();
+
+        let mut ranges = vec![];
+        for file in self.get_non_system_files() {
+            let mut stack: Vec<Conditional> = vec![];
+            for directive in get_directives(file) {
+                let directive = &directive[..];
+                let location = directive[0].get_location();
+                let end = directive[1].get_range().get_end();
+                match &directive[1].get_spelling()[..] {
+                    name @ "if" | name @ "ifdef" | name @ "ifndef" => {
+                        let ignored = stack.last().map_or(false, |c| {
+                            c.ignored || c.start.is_some()
+                        });
+                        let value = if name == "if" && !ignored {
+                            evaluate(directive)
+                        } else {
+                            None
+                        };
+                        stack.push(Conditional {
+                            start: if value == Some(false) { Some(location) } else { None },
+                            taken: value,
+                            ignored,
+                        });
+                    },
+                    name @ "elif" | name @ "else" => {
+                        let conditional = match stack.last_mut() {
+                            Some(conditional) if !conditional.ignored => conditional,
+                            _ => continue,
+                        };
+
+                        match conditional.taken {
+                            Some(true) => conditional.start = conditional.start.or(Some(location)),
+                            Some(false) => {
+                                let value = if name == "else" {
+                                    Some(true)
+                                } else {
+                                    evaluate(directive)
+                                };
+
+                                if value != Some(false) {
+                                    ranges.push(SourceRange::new(conditional.start.unwrap(), end));
+                                    conditional.start = None;
+                                    conditional.taken = value;
+                                }
+                            },
+                            None => { },
+                        }
+                    },
+                    "endif" => {
+                        if let Some(start) = stack.pop().and_then(|c| c.start) {
+                            ranges.push(SourceRange::new(start, end));
+                        }
+                    },
+                    _ => { },
+                }
+            }
+        }
+
+        ranges.retain(|r| {
+            let start = r.get_start().get_file_location();
+            !skipped.iter().any(|&(file, s, e)| {
+                file == start.file && s <= start.offset && start.offset < e
+            })
+        });
+        ranges
+    }
+
+    /// Returns the AST entity with the smallest source range which contains the supplied source
+    /// location, if any.
+    ///
+    /// Unlike `SourceLocation::get_entity`, this finds the innermost declaration, statement, or
+    /// expression which encloses the supplied source location even if that source location is
+    /// whitespace or a comment. If multiple AST entities have the same smallest source range (e.g.,
+    /// an implicit cast and the expression being cast), the most deeply nested AST entity is
+    /// returned.
+    pub fn get_enclosing_entity(&'i self, location: &SourceLocation<'i>) -> Option<Entity<'i>> {
+        let location = location.get_file_location();
+        let file = location.file?;
+
+        let mut enclosing: Option<(Entity<'i>, u32)> = None;
+        self.get_entity().visit_children(|e, _| {
+            let range = match e.get_range() {
+                Some(range) => range,
+                None => return EntityVisitResult::Continue,
+            };
+
+            let start = range.get_start().get_file_location();
+            let end = range.get_end().get_file_location();
+            if start.file != Some(file) || end.file != Some(file) {
+                return EntityVisitResult::Continue;
+            }
+
+            if start.offset > location.offset || end.offset <= location.offset {
+                return EntityVisitResult::Continue;
+            }
+
+            let length = end.offset - start.offset;
+            if enclosing.map_or(true, |(_, l)| length <= l) {
+                enclosing = Some((e, length));
+            }
+
+            EntityVisitResult::Recurse
+        });
+        enclosing.map(|(e, _)| e)
+    }
+
     /// Returns the entity for this translation unit.
     pub fn get_entity(&'i self) -> Entity<'i> {
         unsafe { Entity::from_raw(clang_getTranslationUnitCursor(self.ptr), self) }
@@ -3043,6 +5613,61 @@ impl<'i> TranslationUnit<'i> {
         file.map(|f| File::from_ptr(f, self))
     }
 
+    /// Returns the namespace declarations in this translation unit.
+    ///
+    /// Every declaration of each namespace is included (e.g., a namespace which is reopened will be
+    /// included once for each time it is declared). See `get_namespaces` for a merged list.
+    pub fn get_namespace_declarations(&'i self) -> Vec<Entity<'i>> {
+        let mut namespaces = vec![];
+        self.get_entity().visit_children(|e, _| {
+            match e.get_kind() {
+                EntityKind::Namespace => {
+                    namespaces.push(e);
+                    EntityVisitResult::Recurse
+                },
+                EntityKind::LinkageSpec | EntityKind::UnexposedDecl => EntityVisitResult::Recurse,
+                _ => EntityVisitResult::Continue,
+            }
+        });
+        namespaces
+    }
+
+    /// Returns the namespaces in this translation unit.
+    ///
+    /// Namespaces which are declared multiple times (e.g., a namespace which is reopened) are
+    /// merged and are only included once. The included AST entity for each namespace is the
+    /// canonical AST entity for that namespace (i.e., its first declaration).
+    pub fn get_namespaces(&'i self) -> Vec<Entity<'i>> {
+        let mut seen = HashSet::new();
+        self.get_namespace_declarations().into_iter().map(|n| n.get_canonical_entity()).filter(|n| {
+            seen.insert(*n)
+        }).collect()
+    }
+
+    /// Returns the `#pragma` directives in this translation unit (e.g., `#pragma pack(1)`).
+    ///
+    /// `libclang` does not expose `#pragma` directives so they are found by tokenizing the files in
+    /// this translation unit. Files which are system headers are not searched and `_Pragma`
+    /// operators are not included.
+    pub fn get_pragmas(&'i self) -> Vec<Pragma<'i>> {
+        let mut pragmas = vec![];
+        for file in self.get_non_system_files() {
+            for directive in get_directives(file) {
+                if directive[1].get_spelling() != "pragma" {
+                    continue;
+                }
+
+                let end = directive[directive.len() - 1].get_range().get_end();
+                let text = directive.get(2).and_then(|t| {
+                    SourceRange::new(t.get_location(), end).get_text()
+                }).unwrap_or_default();
+                let range = SourceRange::new(directive[0].get_location(), end);
+                pragmas.push(Pragma { text, range });
+            }
+        }
+        pragmas
+    }
+
     /// Returns the memory usage of this translation unit.
     pub fn get_memory_usage(&self) -> HashMap<MemoryUsage, usize> {
         unsafe {
@@ -3072,6 +5697,42 @@ impl<'i> TranslationUnit<'i> {
         }
     }
 
+    /// Returns the string literals in this translation unit along with their decoded values.
+    ///
+    /// Adjacent string literals (e.g., `"a" "b"`) are concatenated into a single string literal.
+    /// With `libclang` 3.9 and later the values are found by evaluating the string literals.
+    /// Otherwise, or if evaluation fails, the values are found by tokenizing the string literals
+    /// and decoding their escape sequences.
+    pub fn get_string_literals(&'i self) -> Vec<(Entity<'i>, String)> {
+        #[cfg(feature="clang_3_9")]
+        fn evaluate(literal: Entity) -> Option<String> {
+            match literal.evaluate() {
+                Some(EvaluationResult::String(string)) => Some(string.to_string_lossy().into()),
+                _ => None,
+            }
+        }
+
+        #[cfg(not(feature="clang_3_9"))]
+        fn evaluate(_: Entity) -> Option<String> {
+            None
+        }
+
+        let mut literals = vec![];
+        self.get_entity().visit_children(|e, _| {
+            if e.get_kind() == EntityKind::StringLiteral {
+                let value = evaluate(e).unwrap_or_else(|| {
+                    e.get_range().map_or(vec![], |r| r.tokenize()).iter()
+                        .filter(|t| t.get_kind() == TokenKind::Literal)
+                        .map(|t| decode_string_literal(&t.get_spelling()))
+                        .collect()
+                });
+                literals.push((e, value));
+            }
+            EntityVisitResult::Recurse
+        });
+        literals
+    }
+
     /// Returns information about the target for this translation unit.
     #[cfg(feature="clang_5_0")]
     pub fn get_target(&self) -> Target {
@@ -3088,6 +5749,95 @@ impl<'i> TranslationUnit<'i> {
         }
     }
 
+    /// Tokenizes the supplied file and returns the semantic categorization of each token, like the
+    /// semantic tokens provided by a Language Server Protocol server.
+    ///
+    /// Identifiers are categorized by the kind of the declaration they refer to and other tokens
+    /// are categorized by their token kind. Punctuation and identifiers which could not be
+    /// categorized are omitted. Lines and columns are one-based, unlike the Language Server
+    /// Protocol.
+    pub fn semantic_tokens(&'i self, file: &File<'i>) -> Vec<SemanticToken> {
+        fn get_type(kind: EntityKind) -> Option<SemanticTokenType> {
+            match kind {
+                EntityKind::Namespace => Some(SemanticTokenType::Namespace),
+                EntityKind::TypedefDecl |
+                EntityKind::TypeAliasDecl => Some(SemanticTokenType::Type),
+                EntityKind::ClassDecl |
+                EntityKind::ClassTemplate |
+                EntityKind::ObjCInterfaceDecl => Some(SemanticTokenType::Class),
+                EntityKind::EnumDecl => Some(SemanticTokenType::Enum),
+                EntityKind::ObjCProtocolDecl => Some(SemanticTokenType::Interface),
+                EntityKind::StructDecl | EntityKind::UnionDecl => Some(SemanticTokenType::Struct),
+                EntityKind::TemplateTypeParameter => Some(SemanticTokenType::TypeParameter),
+                EntityKind::ParmDecl => Some(SemanticTokenType::Parameter),
+                EntityKind::VarDecl => Some(SemanticTokenType::Variable),
+                EntityKind::FieldDecl |
+                EntityKind::ObjCIvarDecl => Some(SemanticTokenType::Property),
+                EntityKind::EnumConstantDecl => Some(SemanticTokenType::EnumMember),
+                EntityKind::FunctionDecl | EntityKind::FunctionTemplate => {
+                    Some(SemanticTokenType::Function)
+                },
+                EntityKind::Method |
+                EntityKind::Constructor |
+                EntityKind::Destructor |
+                EntityKind::ConversionFunction |
+                EntityKind::ObjCInstanceMethodDecl |
+                EntityKind::ObjCClassMethodDecl => Some(SemanticTokenType::Method),
+                EntityKind::MacroDefinition => Some(SemanticTokenType::Macro),
+                _ => None,
+            }
+        }
+
+        #[cfg(feature="clang_6_0")]
+        let size = file.get_contents().map(|c| c.len());
+        #[cfg(not(feature="clang_6_0"))]
+        let size = None;
+        let size = size.or_else(|| fs::metadata(file.get_path()).ok().map(|m| m.len() as usize));
+
+        let end = file.get_offset_location(size.unwrap_or(0) as u32);
+        let tokens = SourceRange::new(file.get_offset_location(0), end).tokenize();
+        let entities = self.annotate(&tokens);
+
+        tokens.iter().zip(entities.iter()).filter_map(|(token, entity)| {
+            let location = token.get_location();
+            let mut modifiers = SemanticTokenModifiers::default();
+
+            let type_ = match token.get_kind() {
+                TokenKind::Comment => SemanticTokenType::Comment,
+                TokenKind::Keyword => SemanticTokenType::Keyword,
+                TokenKind::Literal => {
+                    let spelling = token.get_spelling();
+                    if spelling.contains('"') || spelling.contains('\'') {
+                        SemanticTokenType::String
+                    } else {
+                        SemanticTokenType::Number
+                    }
+                },
+                TokenKind::Identifier => {
+                    let declaration = entity.and_then(|e| e.get_reference())?;
+                    if declaration.get_location() == Some(location) {
+                        modifiers.declaration = true;
+                        modifiers.definition = declaration.is_definition();
+                    }
+                    let type_ = declaration.get_type();
+                    modifiers.readonly = type_.map_or(false, |t| t.is_const_qualified());
+                    get_type(declaration.get_kind())?
+                },
+                TokenKind::Punctuation => return None,
+            };
+
+            let start = location.get_file_location();
+            let end = token.get_range().get_end().get_file_location();
+            let length = end.offset - start.offset;
+            Some(SemanticToken { line: start.line, column: start.column, length, type_, modifiers })
+        }).collect()
+    }
+
+    /// Returns an owned snapshot of the AST of this translation unit.
+    pub fn snapshot(&'i self) -> AstSnapshot {
+        AstSnapshot::new(&self.get_entity())
+    }
+
     /// Returns a completer which runs code completion.
     pub fn completer<F: Into<PathBuf>>(&self, file: F, line: u32, column: u32) -> Completer {
         Completer::new(self, file, line, column)
@@ -3106,6 +5856,58 @@ impl<'i> TranslationUnit<'i> {
         SaveError::from_error(code)
     }
 
+    /// Visits the files in this translation unit (including the main file) and calls the
+    /// supplied function with each file and its inclusion stack.
+    ///
+    /// The inclusion stack of a file contains the locations of the inclusion directives which
+    /// caused the file to be included, starting with the inclusion directive which directly
+    /// included the file and ending with an inclusion directive in the main file. The inclusion
+    /// stack of the main file is empty. Unlike `File::get_includes`, this does not require a
+    /// detailed preprocessing record.
+    pub fn visit_inclusions<F: FnMut(File<'i>, Vec<SourceLocation<'i>>)>(&'i self, mut f: F) {
+        trait InclusionCallback<'tu> {
+            fn call(&mut self, file: File<'tu>, stack: Vec<SourceLocation<'tu>>);
+        }
+
+        impl<'tu, F: FnMut(File<'tu>, Vec<SourceLocation<'tu>>)> InclusionCallback<'tu> for F {
+            fn call(&mut self, file: File<'tu>, stack: Vec<SourceLocation<'tu>>) {
+                self(file, stack)
+            }
+        }
+
+        extern fn visit(
+            file: CXFile, stack: *mut CXSourceLocation, depth: c_uint, data: CXClientData
+        ) {
+            unsafe {
+                let &mut (tu, ref mut callback) =
+                    &mut *(data as *mut (&TranslationUnit, &mut dyn InclusionCallback));
+
+                let stack = if stack.is_null() || depth == 0 {
+                    &[]
+                } else {
+                    slice::from_raw_parts(stack, depth as usize)
+                };
+
+                let stack = stack.iter().map(|l| SourceLocation::from_raw(*l, tu)).collect();
+                callback.call(File::from_ptr(file, tu), stack);
+            }
+        }
+
+        let mut data = (self, &mut f as &mut dyn InclusionCallback);
+        unsafe { clang_getInclusions(self.ptr, visit, utility::addressof(&mut data)); }
+    }
+
+    fn get_non_system_files(&'i self) -> Vec<File<'i>> {
+        let mut files = vec![];
+        let mut seen = HashSet::new();
+        self.visit_inclusions(|f, _| {
+            if !f.get_offset_location(0).is_in_system_header() && seen.insert(f) {
+                files.push(f);
+            }
+        });
+        files
+    }
+
     //- Consumers --------------------------------
 
     /// Consumes this translation unit and reparses the source file it was created from with the
@@ -3128,6 +5930,25 @@ impl<'i> TranslationUnit<'i> {
             SourceError::from_error(code).map(|_| self)
         }
     }
+
+    /// Consumes this translation unit, reparses the source file it was created from with the same
+    /// compiler arguments that were used originally, and calls the supplied function with each of
+    /// the diagnostics produced by the reparse.
+    ///
+    /// # Failures
+    ///
+    /// * an error occurs while deserializing an AST file
+    /// * `libclang` crashes
+    /// * an unknown error occurs
+    pub fn reparse_with<F: FnMut(&Diagnostic)>(
+        self, unsaved: &[Unsaved], mut f: F
+    ) -> Result<TranslationUnit<'i>, SourceError> {
+        let tu = self.reparse(unsaved)?;
+        for diagnostic in &tu.get_diagnostics() {
+            f(diagnostic);
+        }
+        Ok(tu)
+    }
 }
 
 impl<'i> Drop for TranslationUnit<'i> {
@@ -3209,12 +6030,43 @@ impl<'tu> Type<'tu> {
         SizeofError::from_error(sizeof_).map(|_| sizeof_ as usize)
     }
 
+    /// Returns the size of this type in bytes, retrying with the type of the definition of the
+    /// declaration of this type if this type is an incomplete type.
+    ///
+    /// # Failures
+    ///
+    /// * this type is a dependent type
+    /// * this type is an incomplete type and there is no definition for it in the translation unit
+    /// * this type is a variable size type
+    pub fn get_sizeof_via_definition(&self) -> Result<usize, SizeofError> {
+        match self.get_sizeof() {
+            Err(SizeofError::Incomplete) => {
+                let definition = self.get_canonical_type().get_declaration().and_then(|d| {
+                    d.get_definition()
+                });
+                match definition.and_then(|d| d.get_type()) {
+                    Some(type_) => type_.get_sizeof(),
+                    None => Err(SizeofError::Incomplete),
+                }
+            },
+            result => result,
+        }
+    }
+
     /// Returns the address space of this type.
     #[cfg(feature="clang_5_0")]
     pub fn get_address_space(&self) -> usize {
         unsafe { clang_getAddressSpace(self.raw) as usize }
     }
 
+    /// Returns the OpenCL address space of this type.
+    ///
+    /// See `AddressSpace` for the caveats of this mapping.
+    #[cfg(feature="clang_5_0")]
+    pub fn get_named_address_space(&self) -> AddressSpace {
+        AddressSpace::from_raw(self.get_address_space())
+    }
+
     /// Returns the argument types for this function or method type, if applicable.
     pub fn get_argument_types(&self) -> Option<Vec<Type<'tu>>> {
         iter_option!(
@@ -3223,6 +6075,43 @@ impl<'tu> Type<'tu> {
         ).map(|i| i.map(|t| Type::from_raw(t, self.tu)).collect())
     }
 
+    /// Returns the sizes of the dimensions of this array type from outermost to innermost, if
+    /// applicable.
+    ///
+    /// For example, the dimensions of `int[3][4]` are `[Some(3), Some(4)]`. The size of a dimension
+    /// is `None` if it is incomplete, variable, or dependent. The dimensions are determined from
+    /// the canonical type for this type, so typedefs of array types are also supported.
+    pub fn get_array_dimensions(&self) -> Option<Vec<Option<usize>>> {
+        let mut type_ = self.get_canonical_type();
+        if !type_.is_array() {
+            return None;
+        }
+
+        let mut dimensions = vec![];
+        while type_.is_array() {
+            dimensions.push(type_.get_size());
+            type_ = type_.get_element_type()?.get_canonical_type();
+        }
+        Some(dimensions)
+    }
+
+    /// Returns the innermost element type of this (possibly multidimensional) array type, if
+    /// applicable.
+    ///
+    /// For example, the innermost element type of `int[3][4]` is `int`. The returned type is a
+    /// canonical type.
+    pub fn get_array_element_base(&self) -> Option<Type<'tu>> {
+        let mut type_ = self.get_canonical_type();
+        if !type_.is_array() {
+            return None;
+        }
+
+        while type_.is_array() {
+            type_ = type_.get_element_type()?.get_canonical_type();
+        }
+        Some(type_)
+    }
+
     /// Returns the calling convention specified for this function type, if applicable.
     pub fn get_calling_convention(&self) -> Option<CallingConvention> {
         unsafe {
@@ -3261,6 +6150,16 @@ impl<'tu> Type<'tu> {
         unsafe { clang_getElementType(self.raw).map(|t| Type::from_raw(t, self.tu)) }
     }
 
+    /// Returns the underlying type of this enum type, if applicable.
+    pub fn get_enum_underlying_type(&self) -> Option<Type<'tu>> {
+        let canonical = self.get_canonical_type();
+        if canonical.get_kind() != TypeKind::Enum {
+            return None;
+        }
+
+        canonical.get_declaration()?.get_enum_underlying_type()
+    }
+
     /// Returns the exception specification of this type, if applicable.
     #[cfg(feature="clang_5_0")]
     pub fn get_exception_specification(&self) -> Option<ExceptionSpecification> {
@@ -3272,6 +6171,57 @@ impl<'tu> Type<'tu> {
         }
     }
 
+    /// Returns the result type, argument types, variadicness, calling convention, and exception
+    /// specification of this function prototype type, if applicable.
+    pub fn get_function_info(&self) -> Option<FunctionTypeInfo<'tu>> {
+        if self.get_canonical_type().get_kind() != TypeKind::FunctionPrototype {
+            return None;
+        }
+
+        Some(FunctionTypeInfo {
+            result_type: self.get_result_type()?,
+            argument_types: self.get_argument_types()?,
+            variadic: self.is_variadic(),
+            calling_convention: self.get_calling_convention(),
+            #[cfg(feature="clang_5_0")]
+            exception_specification: self.get_exception_specification(),
+        })
+    }
+
+    /// Returns the layouts of the fields in this record type in declaration order, if applicable.
+    ///
+    /// Returns `None` if this type is not a record type or if the layout of any field could not
+    /// be determined (e.g., a field of an incomplete or dependent type).
+    #[cfg(feature="clang_3_7")]
+    pub fn get_field_layouts(&self) -> Option<Vec<FieldLayout<'tu>>> {
+        let mut layouts = vec![];
+        let mut valid = true;
+        self.visit_fields(|field| {
+            let layout = field.get_type().and_then(|type_| {
+                let size_bits = match field.get_bit_field_width() {
+                    Some(width) => width,
+                    None => type_.get_sizeof().ok()? * 8,
+                };
+
+                Some(FieldLayout {
+                    name: field.get_name(),
+                    type_,
+                    offset_bits: field.get_offset_of_field().ok()?,
+                    size_bits,
+                    align_bits: type_.get_alignof().ok()? * 8,
+                })
+            });
+
+            match layout {
+                Some(layout) => layouts.push(layout),
+                None => valid = false,
+            }
+            valid
+        })?;
+
+        if valid { Some(layouts) } else { None }
+    }
+
     /// Returns the fields in this record type, if applicable.
     #[cfg(feature="clang_3_7")]
     pub fn get_fields(&self) -> Option<Vec<Entity<'tu>>> {
@@ -3316,6 +6266,22 @@ impl<'tu> Type<'tu> {
         unsafe { clang_Type_getObjCObjectBaseType(self.raw).map(|t| Type::from_raw(t, self.tu)) }
     }
 
+    /// Returns the base type, type arguments, and protocols of this Objective-C object type, if
+    /// applicable.
+    #[cfg(feature="clang_8_0")]
+    pub fn get_objc_object_info(&self) -> Option<ObjCObjectInfo<'tu>> {
+        if self.get_kind() != TypeKind::ObjCObject {
+            return None;
+        }
+
+        let base_type = unsafe { clang_Type_getObjCObjectBaseType(self.raw) };
+        base_type.map(|t| ObjCObjectInfo {
+            base_type: Type::from_raw(t, self.tu),
+            type_arguments: self.get_objc_type_arguments(),
+            protocols: self.get_objc_protocol_declarations(),
+        })
+    }
+
     /// Returns the declarations for all protocol references for this Objective-C type, if applicable.
     #[cfg(feature="clang_8_0")]
     pub fn get_objc_protocol_declarations(&self) -> Vec<Entity<'tu>> {
@@ -3334,11 +6300,39 @@ impl<'tu> Type<'tu> {
         ).map(|t| Type::from_raw(t, self.tu)).collect()
     }
 
+    /// Returns the typedef declaration which names this anonymous record or enum type, if
+    /// applicable (e.g., `T` in `typedef struct { int x; } T;`).
+    ///
+    /// Anonymous types which are named by a typedef are displayed with the name of that typedef,
+    /// which is used to find the typedef declaration among the siblings of the type declaration.
+    pub fn get_owning_typedef(&self) -> Option<Entity<'tu>> {
+        let canonical = self.get_canonical_type();
+        let declaration = canonical.get_declaration()?;
+
+        let name = canonical.get_display_name();
+        if declaration.get_name().as_ref() == Some(&name) {
+            return None;
+        }
+
+        declaration.get_lexical_parent()?.get_children().into_iter().find(|c| {
+            let kind = c.get_kind();
+            let typedef = kind == EntityKind::TypedefDecl || kind == EntityKind::TypeAliasDecl;
+            typedef && c.get_name().as_ref() == Some(&name) && c.get_typedef_underlying_type()
+                .and_then(|t| t.get_canonical_type().get_declaration()) == Some(declaration)
+        })
+    }
+
     /// Returns the pointee type for this pointer type, if applicable.
     pub fn get_pointee_type(&self) -> Option<Type<'tu>> {
         unsafe { clang_getPointeeType(self.raw).map(|t| Type::from_raw(t, self.tu)) }
     }
 
+    /// Returns whether the declaration of this record type is a `struct`, `union`, or `class`, if
+    /// applicable.
+    pub fn get_record_kind(&self) -> Option<RecordKind> {
+        self.get_declaration().and_then(|d| d.get_record_kind())
+    }
+
     /// Returns the ref qualifier for this C++ function or method type, if applicable.
     pub fn get_ref_qualifier(&self) -> Option<RefQualifier> {
         unsafe {
@@ -3373,12 +6367,86 @@ impl<'tu> Type<'tu> {
         ).map(|i| i.map(|t| t.map(|t| Type::from_raw(t, self.tu))).collect())
     }
 
+    /// Returns the name of each typedef in the chain of typedefs starting with this type paired
+    /// with the type that typedef is a typedef of (e.g., `[("B", A), ("A", int)]` for `B` in
+    /// `typedef int A; typedef A B;`).
+    ///
+    /// The chain is followed with `get_typedef_declaration` and
+    /// `Entity::get_typedef_underlying_type` until a type which is not a typedef type is reached,
+    /// so the type in the last pair is the first type in the chain which is not a typedef type. If
+    /// this type is not a typedef type, the returned `Vec` will be empty.
+    ///
+    /// Typedef cycles are not possible in valid code but the length of the chain is capped at 256
+    /// typedefs just in case.
+    pub fn get_typedef_chain(&self) -> Vec<(String, Type<'tu>)> {
+        const MAXIMUM_DEPTH: usize = 256;
+
+        let mut chain = vec![];
+        let mut type_ = *self;
+        while chain.len() < MAXIMUM_DEPTH {
+            let declaration = match type_.get_typedef_declaration() {
+                Some(declaration) => declaration,
+                None => break,
+            };
+
+            match declaration.get_typedef_underlying_type() {
+                Some(underlying) => {
+                    chain.push((declaration.get_name().unwrap_or_default(), underlying));
+                    type_ = underlying;
+                },
+                None => break,
+            }
+        }
+        chain
+    }
+
+    /// Returns the typedef or type alias declaration which declared this typedef type, if
+    /// applicable.
+    pub fn get_typedef_declaration(&self) -> Option<Entity<'tu>> {
+        self.get_declaration().filter(|d| {
+            let kind = d.get_kind();
+            kind == EntityKind::TypedefDecl || kind == EntityKind::TypeAliasDecl
+        })
+    }
+
     /// Returns the typedef name of this type, if applicable.
     #[cfg(feature="clang_5_0")]
     pub fn get_typedef_name(&self) -> Option<String> {
         unsafe { utility::to_string_option(clang_getTypedefName(self.raw)) }
     }
 
+    /// Returns the USR of the declaration of the canonical type for this type, if any.
+    ///
+    /// Since the canonical type is used, types which refer to the same record or enum type (e.g.,
+    /// through typedefs) have the same USR. `None` will be returned for builtin types.
+    pub fn get_usr(&self) -> Option<Usr> {
+        self.get_canonical_type().get_declaration().and_then(|d| d.get_usr())
+    }
+
+    /// Returns the size of this vector type in bytes, if applicable.
+    ///
+    /// The size is computed from the number of elements and the size of the element type of the
+    /// canonical type for this type, so typedefs of vector types are also supported.
+    pub fn get_vector_byte_size(&self) -> Option<usize> {
+        let canonical = self.get_canonical_type();
+        match canonical.get_kind() {
+            TypeKind::Vector | TypeKind::ExtVector => {
+                let size = canonical.get_size()?;
+                let element = canonical.get_element_type()?.get_sizeof().ok()?;
+                Some(size * element)
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns whether the declaration of this type is anonymous, including records and enums
+    /// which are only named by a typedef (e.g., `typedef struct { int x; } T;`).
+    #[cfg(feature="clang_3_7")]
+    pub fn is_anonymous(&self) -> bool {
+        let declaration = self.get_canonical_type().get_declaration();
+        declaration.map_or(false, |d| d.is_anonymous() || self.get_owning_typedef().is_some())
+    }
+
     /// Returns whether this type is qualified with const.
     pub fn is_const_qualified(&self) -> bool {
         unsafe { clang_isConstQualifiedType(self.raw) != 0 }
@@ -3395,6 +6463,11 @@ impl<'tu> Type<'tu> {
         }
     }
 
+    /// Returns whether this type is an lvalue reference type (e.g., `int&`).
+    pub fn is_lvalue_reference(&self) -> bool {
+        self.get_canonical_type().get_kind() == TypeKind::LValueReference
+    }
+
     /// Returns whether this type is plain old data (POD).
     pub fn is_pod(&self) -> bool {
         unsafe { clang_isPODType(self.raw) != 0 }
@@ -3405,6 +6478,11 @@ impl<'tu> Type<'tu> {
         unsafe { clang_isRestrictQualifiedType(self.raw) != 0 }
     }
 
+    /// Returns whether this type is an rvalue reference type (e.g., `int&&`).
+    pub fn is_rvalue_reference(&self) -> bool {
+        self.get_canonical_type().get_kind() == TypeKind::RValueReference
+    }
+
     /// Returns whether this type is a transparent tag typedef.
     #[cfg(feature="clang_5_0")]
     pub fn is_transparent_tag(&self) -> bool {
@@ -3421,6 +6499,41 @@ impl<'tu> Type<'tu> {
         unsafe { clang_isVolatileQualifiedType(self.raw) != 0 }
     }
 
+    /// Returns whether this type and the supplied type are specializations of the same template
+    /// with the same template arguments (e.g., two references to `std::vector<int>`).
+    ///
+    /// The canonical declarations of the canonical types for the types and the canonical types of
+    /// the template arguments are compared. `false` will be returned if either type is not a
+    /// template specialization.
+    pub fn specialization_equals(&self, other: &Type) -> bool {
+        fn get_specialization<'tu>(
+            type_: &Type<'tu>
+        ) -> Option<(Entity<'tu>, Vec<Option<Type<'tu>>>)> {
+            let canonical = type_.get_canonical_type();
+            let declaration = canonical.get_declaration()?;
+            declaration.get_template()?;
+            let arguments = canonical.get_template_argument_types()?;
+            let arguments = arguments.iter().map(|a| a.map(|a| a.get_canonical_type())).collect();
+            Some((declaration.get_canonical_entity(), arguments))
+        }
+
+        match (get_specialization(self), get_specialization(other)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Returns the type named by this elaborated type or this type if it is not an elaborated type
+    /// (e.g., `S` for `struct S`).
+    #[cfg(feature="clang_3_9")]
+    pub fn strip_elaborated(&self) -> Type<'tu> {
+        if self.get_kind() == TypeKind::Elaborated {
+            self.get_elaborated_type().unwrap_or(*self)
+        } else {
+            *self
+        }
+    }
+
     /// Visits the fields in this record type, returning `None` if this type is not a record type
     /// and returning `Some(b)` otherwise where `b` indicates whether visitation was ended by the
     /// callback returning `false`.
@@ -3462,6 +6575,36 @@ impl<'tu> Type<'tu> {
 
     //- Categorization ---------------------------
 
+    /// Returns whether this type is an aggregate type (i.e., a record or array type).
+    ///
+    /// This is determined by the kind of the canonical type for this type.
+    pub fn is_aggregate(&self) -> bool {
+        matches!(
+            self.get_canonical_type().get_kind(),
+            TypeKind::Record | TypeKind::ConstantArray | TypeKind::DependentSizedArray |
+            TypeKind::IncompleteArray | TypeKind::VariableArray
+        )
+    }
+
+    fn is_array(&self) -> bool {
+        matches!(
+            self.get_kind(),
+            TypeKind::ConstantArray | TypeKind::DependentSizedArray | TypeKind::IncompleteArray |
+            TypeKind::VariableArray
+        )
+    }
+
+    /// Returns whether this type is a floating point type.
+    ///
+    /// This is determined by the kind of the canonical type for this type.
+    pub fn is_floating_point(&self) -> bool {
+        matches!(
+            self.get_canonical_type().get_kind(),
+            TypeKind::Half | TypeKind::Float16 | TypeKind::Float | TypeKind::Double |
+            TypeKind::LongDouble | TypeKind::Float128
+        )
+    }
+
     /// Returns whether this type is an integer type.
     pub fn is_integer(&self) -> bool {
         self.raw.kind >= CXType_Bool && self.raw.kind <= CXType_Int128
@@ -3476,6 +6619,40 @@ impl<'tu> Type<'tu> {
     pub fn is_unsigned_integer(&self) -> bool {
         self.raw.kind >= CXType_Bool && self.raw.kind <= CXType_UInt128
     }
+
+    /// Returns whether this type is a scalar type (i.e., an arithmetic, enum, pointer, or
+    /// `nullptr_t` type).
+    ///
+    /// This is determined by the kind of the canonical type for this type.
+    pub fn is_scalar(&self) -> bool {
+        let canonical = self.get_canonical_type();
+        if canonical.is_integer() || canonical.is_floating_point() {
+            return true;
+        }
+
+        matches!(
+            canonical.get_kind(),
+            TypeKind::Complex | TypeKind::Enum | TypeKind::Nullptr | TypeKind::Pointer |
+            TypeKind::BlockPointer | TypeKind::MemberPointer | TypeKind::ObjCObjectPointer |
+            TypeKind::ObjCId | TypeKind::ObjCClass | TypeKind::ObjCSel
+        )
+    }
+
+    /// Returns how a parameter of this type is passed.
+    ///
+    /// This is determined by the kind of the canonical type for this type. Whether the referenced
+    /// or pointed to type is const can be determined with `get_pointee_type` and
+    /// `is_const_qualified`.
+    pub fn parameter_passing(&self) -> ParameterPassing {
+        match self.get_canonical_type().get_kind() {
+            TypeKind::LValueReference => ParameterPassing::ByLValueRef,
+            TypeKind::RValueReference => ParameterPassing::ByRValueRef,
+            TypeKind::Pointer | TypeKind::BlockPointer | TypeKind::ObjCObjectPointer => {
+                ParameterPassing::ByPointer
+            },
+            _ => ParameterPassing::ByValue,
+        }
+    }
 }
 
 impl<'tu> fmt::Debug for Type<'tu> {
@@ -3611,7 +6788,311 @@ impl Version {
 // Functions
 //================================================
 
+/// Returns the changes to the top-level declarations between two translation units.
+///
+/// This comparison is shallow and best-effort. Declarations are matched by their kind and USR and
+/// declarations without a USR are ignored. Matched declarations are considered modified if the
+/// tokens that make up the declarations differ. See `ast_diff_with_depth` for a deeper comparison.
+pub fn ast_diff<'tu>(
+    a: &'tu TranslationUnit<'tu>, b: &'tu TranslationUnit<'tu>
+) -> Vec<AstChange<'tu>> {
+    ast_diff_with_depth(a, b, 0)
+}
+
+/// Returns the changes to the declarations between two translation units.
+///
+/// This behaves like `ast_diff` except that the children of matched declarations are also compared
+/// down to the supplied depth (e.g., a depth of `1` will compare the fields of matched structs).
+pub fn ast_diff_with_depth<'tu>(
+    a: &'tu TranslationUnit<'tu>, b: &'tu TranslationUnit<'tu>, depth: usize
+) -> Vec<AstChange<'tu>> {
+    let mut changes = vec![];
+    diff(a.get_entity().get_children(), b.get_entity().get_children(), depth, &mut changes);
+    changes
+}
+
+/// Returns the value of the supplied string literal token with its escape sequences decoded.
+fn decode_string_literal(literal: &str) -> String {
+    fn push_char(bytes: &mut Vec<u8>, c: char) {
+        bytes.extend(c.encode_utf8(&mut [0; 4]).as_bytes());
+    }
+
+    let start = literal.find('"').unwrap_or(0);
+    let end = literal.rfind('"').unwrap_or(0);
+    if start >= end {
+        return String::new();
+    }
+
+    let contents = &literal[start + 1..end];
+    if literal[..start].ends_with('R') {
+        let delimiter = contents.find('(').unwrap_or(0);
+        return contents.get(delimiter + 1..contents.len() - delimiter - 1).unwrap_or("").into();
+    }
+
+    let mut bytes = vec![];
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            push_char(&mut bytes, c);
+            continue;
+        }
+
+        let (radix, limit) = match chars.peek().cloned() {
+            Some('0'..='7') => (8, 3),
+            Some('x') => (16, usize::MAX),
+            Some('u') => (16, 4),
+            Some('U') => (16, 8),
+            Some(c) => {
+                chars.next();
+                match c {
+                    'a' => bytes.push(7),
+                    'b' => bytes.push(8),
+                    'f' => bytes.push(12),
+                    'n' => bytes.push(b'\n'),
+                    'r' => bytes.push(b'\r'),
+                    't' => bytes.push(b'\t'),
+                    'v' => bytes.push(11),
+                    c => push_char(&mut bytes, c),
+                }
+                continue;
+            },
+            None => break,
+        };
+
+        let unicode = matches!(chars.peek(), Some('u') | Some('U'));
+        if radix == 16 {
+            chars.next();
+        }
+
+        let mut value = 0u32;
+        for _ in 0..limit {
+            match chars.peek().and_then(|c| c.to_digit(radix)) {
+                Some(digit) => value = value.wrapping_mul(radix).wrapping_add(digit),
+                None => break,
+            }
+            chars.next();
+        }
+
+        if unicode {
+            push_char(&mut bytes, std::char::from_u32(value).unwrap_or('\u{FFFD}'));
+        } else {
+            bytes.push(value as u8);
+        }
+    }
+    String::from_utf8_lossy(&bytes).into()
+}
+
+fn diff<'tu>(
+    a: Vec<Entity<'tu>>, b: Vec<Entity<'tu>>, depth: usize, changes: &mut Vec<AstChange<'tu>>
+) {
+    fn key(entity: &Entity) -> Option<(EntityKind, String)> {
+        entity.get_usr().map(|u| (entity.get_kind(), u.0))
+    }
+
+    fn tokens(entity: &Entity) -> Vec<String> {
+        let tokens = entity.get_range().map(|r| r.tokenize()).unwrap_or_default();
+        tokens.iter().map(|t| t.get_spelling()).collect()
+    }
+
+    let mut indices: HashMap<_, Vec<usize>> = HashMap::new();
+    for (index, entity) in b.iter().enumerate() {
+        if let Some(key) = key(entity) {
+            indices.entry(key).or_default().push(index);
+        }
+    }
+
+    let mut matched = vec![false; b.len()];
+    for old in a {
+        let index = key(&old).and_then(|k| indices.get_mut(&k)).and_then(|is| {
+            if is.is_empty() { None } else { Some(is.remove(0)) }
+        });
+
+        if let Some(index) = index {
+            matched[index] = true;
+            let new = b[index];
+            if tokens(&old) != tokens(&new) {
+                changes.push(AstChange::Modified(old, new));
+            }
+            if depth != 0 {
+                diff(old.get_children(), new.get_children(), depth - 1, changes);
+            }
+        } else if key(&old).is_some() {
+            changes.push(AstChange::Removed(old));
+        }
+    }
+
+    for (index, new) in b.into_iter().enumerate() {
+        if !matched[index] && key(&new).is_some() {
+            changes.push(AstChange::Added(new));
+        }
+    }
+}
+
+/// Returns the preprocessing directives in the supplied file as the tokens on each directive line.
+fn get_directives(file: File) -> Vec<Vec<Token>> {
+    let tokens = tokenize_file(file);
+    let lines = tokens.iter().map(|t| t.get_location().get_file_location().line);
+    let lines = lines.collect::<Vec<_>>();
+
+    let mut directives = vec![];
+    for (index, token) in tokens.iter().enumerate() {
+        if token.get_spelling() != "#" || (index != 0 && lines[index - 1] == lines[index]) {
+            continue;
+        }
+
+        let length = lines[index..].iter().take_while(|&&l| l == lines[index]).count();
+        if length >= 2 {
+            directives.push(tokens[index..index + length].to_vec());
+        }
+    }
+    directives
+}
+
+/// Returns the offset in the supplied file of the end of its preamble (i.e., the offset of the
+/// first token which is not part of a leading preprocessing directive).
+fn get_preamble_end(file: File) -> u32 {
+    let tokens = tokenize_file(file);
+    let lines = tokens.iter().map(|t| t.get_location().get_file_location().line);
+    let lines = lines.collect::<Vec<_>>();
+
+    let mut index = 0;
+    while index < tokens.len() && tokens[index].get_spelling() == "#" {
+        let line = lines[index];
+        index += lines[index..].iter().take_while(|&&l| l == line).count();
+    }
+
+    match tokens.get(index) {
+        Some(token) => token.get_location().get_file_location().offset,
+        None => u32::MAX,
+    }
+}
+
+/// Returns the path to the resource directory of the `libclang` in use, if it can be found.
+///
+/// The resource directory contains the builtin headers (e.g., `stddef.h`) in its `include`
+/// subdirectory and can be supplied to a parser with `-resource-dir`. When the `runtime` feature is
+/// enabled and a `libclang` shared library is loaded on this thread (i.e., a `Clang` instance
+/// exists), the resource directory is searched for in the `clang/<version>` subdirectories of the
+/// directory which contains that shared library. Otherwise, or if this search fails, the resource
+/// directory is determined from the header search paths of the first `clang` executable found by
+/// `clang-sys` (which requires running that executable). Neither strategy is guaranteed to find the
+/// resource directory that matches the `libclang` in use (e.g., when multiple versions of `clang`
+/// are installed or `libclang` was relocated).
+pub fn get_resource_dir() -> Option<PathBuf> {
+    fn version(path: &Path) -> Vec<u32> {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        name.split('.').filter_map(|c| c.parse().ok()).collect()
+    }
+
+    fn find(directory: &Path) -> Option<PathBuf> {
+        let entries = fs::read_dir(directory.join("clang")).ok()?;
+        let paths = entries.filter_map(|e| e.ok()).map(|e| e.path());
+        let paths = paths.filter(|p| p.join("include").join("stddef.h").is_file());
+        paths.max_by_key(|p| version(p))
+    }
+
+    #[cfg(feature="runtime")]
+    fn find_runtime() -> Option<PathBuf> {
+        let library = clang_sys::get_library()?;
+        let path = fs::canonicalize(library.path()).ok()?;
+        find(path.parent()?)
+    }
+
+    #[cfg(not(feature="runtime"))]
+    fn find_runtime() -> Option<PathBuf> {
+        None
+    }
+
+    find_runtime().or_else(|| {
+        let clang = clang_sys::support::Clang::find(None, &[])?;
+        let paths = clang.c_search_paths.unwrap_or_default();
+        let include = paths.into_iter().find(|p| {
+            p.ends_with("include") && p.join("stddef.h").is_file()
+        });
+
+        match include {
+            Some(include) => include.parent().map(|p| p.to_path_buf()),
+            None => {
+                let path = fs::canonicalize(&clang.path).ok()?;
+                find(&path.parent()?.parent()?.join("lib"))
+            },
+        }
+    })
+}
+
 /// Returns the version string for the version of `libclang` in use.
 pub fn get_version() -> String {
     unsafe { utility::to_string(clang_getClangVersion()) }
 }
+
+/// Parses the supplied C++ source code with the default parser options.
+///
+/// The source code is provided to `libclang` as the unsaved contents of a file named `input.cpp` so
+/// no files are created or read. The returned translation unit owns the index it was parsed with.
+///
+/// # Failures
+///
+/// * an error occurs while deserializing an AST file
+/// * `libclang` crashes
+/// * an unknown error occurs
+pub fn parse_cpp<'c>(
+    clang: &'c Clang, source: &str
+) -> Result<OwnedTranslationUnit<'c>, SourceError> {
+    let index = Index::new(clang, false, false);
+    let ptr = {
+        let tu = index.parser("input.cpp").unsaved(&[Unsaved::new("input.cpp", source)]).parse()?;
+        let ptr = tu.ptr;
+        mem::forget(tu);
+        ptr
+    };
+    Ok(OwnedTranslationUnit { tu: TranslationUnit::from_ptr(ptr), _index: index })
+}
+
+/// Returns the supplied comment with its delimiters and its common indentation removed.
+fn strip_comment_markers(comment: &str) -> String {
+    fn strip_prefix<'a>(line: &'a str, prefixes: &[&str]) -> &'a str {
+        prefixes.iter().find(|p| line.starts_with(*p)).map_or(line, |p| &line[p.len()..])
+    }
+
+    let mut lines = vec![];
+    let mut block = false;
+    for line in comment.lines() {
+        let mut line = line.trim_start();
+        if !block && line.starts_with("//") {
+            line = strip_prefix(line, &["///<", "//!<", "///", "//!", "//"]);
+        } else {
+            if !block && line.starts_with("/*") {
+                line = strip_prefix(line, &["/**<", "/*!<", "/**", "/*!", "/*"]);
+                block = true;
+            } else if block && line.starts_with('*') && !line.starts_with("*/") {
+                line = &line[1..];
+            }
+
+            if block && line.trim_end().ends_with("*/") {
+                let end = line.trim_end();
+                line = end[..end.len() - 2].trim_end_matches('*');
+                block = false;
+            }
+        }
+        lines.push(line.trim_end());
+    }
+
+    let indentation = lines.iter().filter(|l| !l.is_empty()).map(|l| {
+        l.len() - l.trim_start().len()
+    }).min().unwrap_or(0);
+    let lines = lines.iter().map(|l| l.get(indentation..).unwrap_or("")).collect::<Vec<_>>();
+
+    let start = lines.iter().position(|l| !l.is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|l| !l.is_empty()).map_or(start, |e| e + 1);
+    lines[start..end].join("\n")
+}
+
+/// Returns the tokens in the supplied file.
+fn tokenize_file(file: File) -> Vec<Token> {
+    #[cfg(feature="clang_6_0")]
+    let contents = file.get_contents().or_else(|| fs::read_to_string(file.get_path()).ok());
+    #[cfg(not(feature="clang_6_0"))]
+    let contents = fs::read_to_string(file.get_path()).ok();
+
+    file.tokenize_range(0, contents.map_or(0, |c| c.len()))
+}
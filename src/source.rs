@@ -16,6 +16,7 @@
 
 use std::cmp;
 use std::fmt;
+use std::fs;
 use std::hash;
 use std::mem;
 use std::slice;
@@ -114,6 +115,86 @@ impl<'tu> File<'tu> {
         unsafe { clang_isFileMultipleIncludeGuarded(self.tu.ptr, self.ptr) != 0 }
     }
 
+    /// Returns whether the supplied path refers to this file.
+    ///
+    /// The unique identifiers of the files are compared rather than the paths so that this method
+    /// handles paths that are not normalized or that pass through symbolic links.
+    pub fn path_equivalent<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.tu.get_file(path).map_or(false, |f| f.get_id() == self.get_id())
+    }
+
+    /// Returns the tokens in the supplied range of character offsets in this file.
+    ///
+    /// Tokens which start in the range but end after it are included. If either offset is not a
+    /// valid location in this file (e.g., it is past the end of this file) or `start_offset` is
+    /// greater than `end_offset`, the returned `Vec` will be empty.
+    pub fn tokenize_range(&self, start_offset: usize, end_offset: usize) -> Vec<Token<'tu>> {
+        if start_offset > end_offset {
+            return vec![];
+        }
+
+        let start = self.get_offset_location(start_offset as u32);
+        let end = self.get_offset_location(end_offset as u32);
+        if start.get_file_location().file.is_none() || end.get_file_location().file.is_none() {
+            return vec![];
+        }
+
+        SourceRange::new(start, end).tokenize()
+    }
+
+    /// Returns the token in this file which contains the supplied source location, if any.
+    ///
+    /// Only the region of this file within 256 bytes of the supplied source location is tokenized.
+    /// Comments are tokenized as `TokenKind::Comment` tokens so a comment token will be returned if
+    /// the supplied source location is in a comment. `None` will be returned if the supplied
+    /// source location is not in this file or is not in a token (e.g., it is whitespace).
+    pub fn get_token_at(&self, location: &SourceLocation<'tu>) -> Option<Token<'tu>> {
+        let (offset, tokens) = self.tokenize_around(location)?;
+        tokens.into_iter().find(|t| {
+            let (start, end) = get_token_offsets(t);
+            start <= offset && offset < end
+        })
+    }
+
+    /// Returns the first token in this file which starts after the supplied source location, if
+    /// any.
+    ///
+    /// Only the region of this file within 256 bytes of the supplied source location is tokenized.
+    /// `None` will be returned if the supplied source location is not in this file or if there is
+    /// no such token in that region.
+    pub fn get_next_token(&self, location: &SourceLocation<'tu>) -> Option<Token<'tu>> {
+        let (offset, tokens) = self.tokenize_around(location)?;
+        tokens.into_iter().find(|t| get_token_offsets(t).0 > offset)
+    }
+
+    /// Returns the last token in this file which ends at or before the supplied source location,
+    /// if any.
+    ///
+    /// Only the region of this file within 256 bytes of the supplied source location is tokenized.
+    /// `None` will be returned if the supplied source location is not in this file or if there is
+    /// no such token in that region.
+    pub fn get_previous_token(&self, location: &SourceLocation<'tu>) -> Option<Token<'tu>> {
+        let (offset, tokens) = self.tokenize_around(location)?;
+        tokens.into_iter().rev().find(|t| get_token_offsets(t).1 <= offset)
+    }
+
+    fn tokenize_around(&self, location: &SourceLocation<'tu>) -> Option<(usize, Vec<Token<'tu>>)> {
+        const WINDOW: usize = 256;
+
+        let location = location.get_file_location();
+        if location.file != Some(*self) {
+            return None;
+        }
+
+        // `libclang` clamps source locations past the last line to the last character.
+        let last = self.get_location(u32::MAX, 1).get_file_location().offset as usize;
+
+        let offset = location.offset as usize;
+        let start = offset.saturating_sub(WINDOW);
+        let end = cmp::min(offset + WINDOW, last + 1);
+        Some((offset, self.tokenize_range(start, end)))
+    }
+
     /// Returns the source location at the supplied line and column in this file.
     ///
     /// # Panics
@@ -333,6 +414,14 @@ impl<'tu> SourceLocation<'tu> {
         unsafe { location!(clang_getFileLocation, self.raw, self.tu) }
     }
 
+    /// Returns the file this source location is in, if any.
+    ///
+    /// This is the file of the location returned by `get_file_location` and will be `None` for
+    /// in-memory and builtin source locations.
+    pub fn get_file(&self) -> Option<File<'tu>> {
+        self.get_file_location().file
+    }
+
     /// Returns the file path, line, and column of this source location taking line directives into
     /// account.
     pub fn get_presumed_location(&self) -> (String, u32, u32) {
@@ -430,6 +519,51 @@ impl<'tu> SourceRange<'tu> {
         unsafe { SourceLocation::from_raw(clang_getRangeEnd(self.raw), self.tu) }
     }
 
+    /// Compares this source range to the supplied source range by the path of the file, then the
+    /// start offset, and then the end offset.
+    ///
+    /// This ordering is not consistent with the `PartialEq` implementation for source ranges (e.g.,
+    /// distinct source ranges in macro expansions may have the same file locations) so it is not
+    /// exposed as an `Ord` implementation. It can be used with `sort_by` and similar methods or
+    /// source ranges can be wrapped in `SourceRangeByFileOffset` where `Ord` is required.
+    pub fn cmp_by_file_offset(&self, other: &SourceRange<'tu>) -> cmp::Ordering {
+        get_file_offset_key(self).cmp(&get_file_offset_key(other))
+    }
+
+    /// Returns the source code covered by this source range, if possible.
+    ///
+    /// Unlike joining the spellings of the tokens returned by `tokenize`, this preserves the
+    /// whitespace and comments in the source code. `None` will be returned if this source range
+    /// spans multiple files or if the contents of the file can't be retrieved.
+    pub fn get_text(&self) -> Option<String> {
+        get_source(self.get_start().get_file_location(), self.get_end().get_file_location())
+    }
+
+    /// Returns the source code between the spelling locations of the start and end of this source
+    /// range, if possible.
+    ///
+    /// Unlike `get_text`, which uses file locations, this returns the source code where the tokens
+    /// in this source range were spelled (e.g., the body of a macro definition for a source range
+    /// in a macro expansion). `None` will be returned if the start and end are spelled in different
+    /// files or if the contents of the file can't be retrieved.
+    pub fn get_contents(&self) -> Option<String> {
+        get_source(self.get_start().get_spelling_location(), self.get_end().get_spelling_location())
+    }
+
+    /// Returns the length of this source range in bytes, if possible.
+    ///
+    /// `None` will be returned if this source range spans multiple files or if the end of this
+    /// source range precedes its start.
+    pub fn len_bytes(&self) -> Option<usize> {
+        let start = self.get_start().get_file_location();
+        let end = self.get_end().get_file_location();
+        if start.file.is_none() || start.file != end.file || end.offset < start.offset {
+            return None;
+        }
+
+        Some((end.offset - start.offset) as usize)
+    }
+
     /// Returns whether this source range is in the main file of its translation unit.
     pub fn is_in_main_file(&self) -> bool {
         self.get_start().is_in_main_file()
@@ -442,6 +576,22 @@ impl<'tu> SourceRange<'tu> {
 
     /// Tokenizes the source code covered by this source range and returns the resulting tokens.
     pub fn tokenize(&self) -> Vec<Token<'tu>> {
+        self.tokenize_with(|t| Token::from_raw(t, self.tu))
+    }
+
+    /// Tokenizes the source code covered by this source range and returns the resulting tokens
+    /// along with their spellings.
+    ///
+    /// This is faster than calling `Token::get_spelling` on each of the tokens returned by
+    /// `tokenize`.
+    pub fn tokenize_with_spellings(&self) -> Vec<(Token<'tu>, String)> {
+        self.tokenize_with(|t| unsafe {
+            let spelling = utility::to_string(clang_getTokenSpelling(self.tu.ptr, t));
+            (Token::from_raw(t, self.tu), spelling)
+        })
+    }
+
+    fn tokenize_with<T, F: FnMut(CXToken) -> T>(&self, f: F) -> Vec<T> {
         unsafe {
             let (mut raw, mut count) = (mem::MaybeUninit::uninit(), mem::MaybeUninit::uninit());
             clang_tokenize(self.tu.ptr, self.raw, raw.as_mut_ptr(), count.as_mut_ptr());
@@ -451,7 +601,7 @@ impl<'tu> SourceRange<'tu> {
             } else {
                 slice::from_raw_parts(raw, count as usize)
             };
-            let tokens = raws.iter().map(|t| Token::from_raw(*t, self.tu)).collect();
+            let tokens = raws.iter().cloned().map(f).collect();
             if !raw.is_null() {
                 clang_disposeTokens(self.tu.ptr, raw, count);
             }
@@ -484,10 +634,69 @@ impl<'tu> hash::Hash for SourceRange<'tu> {
     }
 }
 
+// SourceRangeByFileOffset _______________________
+
+/// A source range which is compared, ordered, and hashed by the path of its file, then its start
+/// offset, and then its end offset (see `SourceRange::cmp_by_file_offset`).
+#[derive(Copy, Clone, Debug)]
+pub struct SourceRangeByFileOffset<'tu>(pub SourceRange<'tu>);
+
+impl<'tu> cmp::PartialEq for SourceRangeByFileOffset<'tu> {
+    fn eq(&self, other: &SourceRangeByFileOffset<'tu>) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl<'tu> cmp::Eq for SourceRangeByFileOffset<'tu> { }
+
+impl<'tu> cmp::PartialOrd for SourceRangeByFileOffset<'tu> {
+    fn partial_cmp(&self, other: &SourceRangeByFileOffset<'tu>) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'tu> cmp::Ord for SourceRangeByFileOffset<'tu> {
+    fn cmp(&self, other: &SourceRangeByFileOffset<'tu>) -> cmp::Ordering {
+        self.0.cmp_by_file_offset(&other.0)
+    }
+}
+
+impl<'tu> hash::Hash for SourceRangeByFileOffset<'tu> {
+    fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
+        get_file_offset_key(&self.0).hash(hasher);
+    }
+}
+
 //================================================
 // Functions
 //================================================
 
+fn get_file_offset_key(range: &SourceRange) -> (Option<PathBuf>, u32, u32) {
+    let start = range.get_start().get_file_location();
+    let end = range.get_end().get_file_location();
+    (start.file.map(|f| f.get_path()), start.offset, end.offset)
+}
+
+fn get_source(start: Location, end: Location) -> Option<String> {
+    let file = start.file?;
+    if end.file != Some(file) {
+        return None;
+    }
+
+    #[cfg(feature="clang_6_0")]
+    let contents = file.get_contents().or_else(|| fs::read_to_string(file.get_path()).ok());
+    #[cfg(not(feature="clang_6_0"))]
+    let contents = fs::read_to_string(file.get_path()).ok();
+
+    contents?.get(start.offset as usize..end.offset as usize).map(|s| s.into())
+}
+
+fn get_token_offsets(token: &Token) -> (usize, usize) {
+    let range = token.get_range();
+    let start = range.get_start().get_file_location().offset as usize;
+    (start, range.get_end().get_file_location().offset as usize)
+}
+
 fn visit<'tu, F, G>(tu: &'tu TranslationUnit<'tu>, f: F, g: G) -> bool
     where F: FnMut(Entity<'tu>, SourceRange<'tu>) -> bool,
           G: Fn(CXCursorAndRangeVisitor) -> CXResult
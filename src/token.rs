@@ -27,6 +27,67 @@ use super::source::{SourceLocation, SourceRange};
 // Enums
 //================================================
 
+// KeywordCategory _______________________________
+
+/// Indicates the categorization of a keyword token.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum KeywordCategory {
+    /// A control flow keyword (e.g., `if` or `return`).
+    Control,
+    /// A type keyword (e.g., `int` or `struct`).
+    Type,
+    /// A storage class keyword (e.g., `static` or `extern`).
+    Storage,
+    /// A preprocessor directive (e.g., `include` or `define`).
+    Preprocessor,
+    /// Any other keyword (e.g., `const` or `sizeof`).
+    Other,
+}
+
+// SemanticTokenType _____________________________
+
+/// Indicates the semantic categorization of a token, modeled after the Language Server Protocol
+/// semantic token types.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SemanticTokenType {
+    /// A namespace name.
+    Namespace,
+    /// A type alias name.
+    Type,
+    /// A class name.
+    Class,
+    /// An enum name.
+    Enum,
+    /// An interface name (e.g., an Objective-C protocol).
+    Interface,
+    /// A struct or union name.
+    Struct,
+    /// A template type parameter name.
+    TypeParameter,
+    /// A function or method parameter name.
+    Parameter,
+    /// A variable name.
+    Variable,
+    /// A field name.
+    Property,
+    /// An enum constant name.
+    EnumMember,
+    /// A function name.
+    Function,
+    /// A method, constructor, destructor, or conversion function name.
+    Method,
+    /// A macro name.
+    Macro,
+    /// A keyword.
+    Keyword,
+    /// A comment.
+    Comment,
+    /// A string or character literal.
+    String,
+    /// A numeric literal.
+    Number,
+}
+
 // TokenKind _____________________________________
 
 /// Indicates the categorization of a token.
@@ -49,6 +110,37 @@ pub enum TokenKind {
 // Structs
 //================================================
 
+// SemanticToken _________________________________
+
+/// The position and semantic categorization of a token.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SemanticToken {
+    /// The line of the start of the token.
+    pub line: u32,
+    /// The column of the start of the token.
+    pub column: u32,
+    /// The length of the token in bytes.
+    pub length: u32,
+    /// The semantic categorization of the token.
+    pub type_: SemanticTokenType,
+    /// The semantic modifiers of the token.
+    pub modifiers: SemanticTokenModifiers,
+}
+
+// SemanticTokenModifiers ________________________
+
+/// The semantic modifiers of a token, modeled after the Language Server Protocol semantic token
+/// modifiers.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SemanticTokenModifiers {
+    /// Indicates whether the token is the name of a declaration.
+    pub declaration: bool,
+    /// Indicates whether the token is the name of a definition.
+    pub definition: bool,
+    /// Indicates whether the token refers to something with a const qualified type.
+    pub readonly: bool,
+}
+
 // Token _________________________________________
 
 /// A lexed piece of a source file.
@@ -87,6 +179,61 @@ impl<'tu> Token<'tu> {
     pub fn get_range(&self) -> SourceRange<'tu> {
         unsafe { SourceRange::from_raw(clang_getTokenExtent(self.tu.ptr, self.raw), self.tu) }
     }
+
+    /// Returns the categorization of this keyword token or preprocessor directive, if applicable.
+    ///
+    /// This categorization is based on the spelling of this token and, for preprocessor
+    /// directives, the tokens that precede this token on the same line.
+    pub fn keyword_category(&self) -> Option<KeywordCategory> {
+        if self.is_preprocessor_directive() {
+            return Some(KeywordCategory::Preprocessor);
+        } else if self.get_kind() != TokenKind::Keyword {
+            return None;
+        }
+
+        let category = match &self.get_spelling()[..] {
+            "break" | "case" | "catch" | "co_await" | "co_return" | "co_yield" | "continue" |
+            "default" | "do" | "else" | "for" | "goto" | "if" | "return" | "switch" | "throw" |
+            "try" | "while" => KeywordCategory::Control,
+            "_Bool" | "_Complex" | "__int128" | "bool" | "char" | "char8_t" | "char16_t" |
+            "char32_t" | "class" | "double" | "enum" | "float" | "int" | "long" | "short" |
+            "signed" | "struct" | "union" | "unsigned" | "void" |
+            "wchar_t" => KeywordCategory::Type,
+            "_Thread_local" | "__thread" | "extern" | "mutable" | "register" | "static" |
+            "thread_local" => KeywordCategory::Storage,
+            _ => KeywordCategory::Other,
+        };
+
+        Some(category)
+    }
+
+    /// Returns whether this token is the name of a preprocessor directive (e.g., `include` in
+    /// `#include <stdio.h>`).
+    ///
+    /// This is determined by the spelling of this token and whether this token is preceded only by
+    /// `#` on the same line.
+    pub fn is_preprocessor_directive(&self) -> bool {
+        const DIRECTIVES: &[&str] = &[
+            "define", "elif", "else", "endif", "error", "if", "ifdef", "ifndef", "import",
+            "include", "include_next", "line", "pragma", "undef", "warning",
+        ];
+
+        if !DIRECTIVES.contains(&&self.get_spelling()[..]) {
+            return false;
+        }
+
+        let location = self.get_location().get_file_location();
+        let file = match location.file {
+            Some(file) => file,
+            None => return false,
+        };
+
+        let range = SourceRange::new(file.get_location(location.line, 1), self.get_location());
+        let tokens = range.tokenize().into_iter().filter(|t| {
+            t.get_location().get_file_location().offset < location.offset
+        }).collect::<Vec<_>>();
+        tokens.len() == 1 && tokens[0].get_spelling() == "#"
+    }
 }
 
 impl<'tu> fmt::Debug for Token<'tu> {
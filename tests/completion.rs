@@ -122,4 +122,17 @@ pub fn test(clang: &Clang) {
             CompletionChunk::Text("::".into()),
         ]);
     });
+
+    let source = "struct A { int a; };\nvoid b() { A a; a. }";
+
+    super::with_temporary_file("test.cpp", source, |_, f| {
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(f).with_editing_defaults().parse().unwrap();
+        let tu = tu.reparse(&[]).unwrap();
+
+        let results = tu.completer(f, 2, 19).complete();
+        assert!(results.get_results().iter().any(|r| {
+            r.kind == EntityKind::FieldDecl && r.string.get_typed_text() == Some("a".into())
+        }));
+    });
 }
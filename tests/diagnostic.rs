@@ -53,4 +53,26 @@ pub fn test(clang: &Clang) {
             FixIt::Replacement(range!(file, 4, 50, 4, 52), ".i = ".into())
         ]);
     });
+
+    let files = &[
+        ("test.hpp", "int a = ;"),
+        ("test.cpp", "#include \"test.hpp\"\nint b = ;"),
+    ];
+
+    super::with_temporary_files(files, |_, fs| {
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(&fs[1]).with_editing_defaults().parse().unwrap();
+        let tu = tu.reparse(&[]).unwrap();
+
+        let header = tu.get_file(&fs[0]).unwrap();
+        let file = tu.get_file(&fs[1]).unwrap();
+
+        let locations = tu.get_diagnostics().iter().map(|d| d.get_location()).collect::<Vec<_>>();
+        assert_eq!(locations, &[header.get_location(1, 9), file.get_location(2, 9)]);
+
+        let locations = tu.get_preamble_diagnostics().iter().map(|d| {
+            d.get_location()
+        }).collect::<Vec<_>>();
+        assert_eq!(locations, &[header.get_location(1, 9)]);
+    });
 }
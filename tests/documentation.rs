@@ -82,4 +82,32 @@ pub fn test(clang: &Clang) {
         ]));
         assert_eq!(children[8], CommentChild::VerbatimCommand(vec![" *nullptr ".into()]));
     });
+    let source = r#"
+        /// Copies some bytes.
+        ///
+        /// \param [out] destination The buffer to copy
+        ///     the bytes into.
+        /// \param source The \c char buffer to copy the bytes from.
+        void copy(char* destination, const char* source);
+        void undocumented(int a);
+    "#;
+
+    super::with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 2);
+
+        assert_eq!(children[0].get_param_docs(), &[
+            ParamDoc {
+                name: "destination".into(),
+                direction: Some(ParameterDirection::Out),
+                text: "The buffer to copy the bytes into.".into(),
+            },
+            ParamDoc {
+                name: "source".into(),
+                direction: None,
+                text: "The char buffer to copy the bytes from.".into(),
+            },
+        ]);
+        assert_eq!(children[1].get_param_docs(), &[]);
+    });
 }
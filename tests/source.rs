@@ -1,3 +1,5 @@
+use std::cmp::{Ordering};
+use std::collections::{BTreeSet};
 use std::ffi::{OsStr};
 use std::path::{Path};
 
@@ -27,6 +29,26 @@ pub fn test(clang: &Clang) {
         assert!(!f.is_include_guarded());
     });
 
+    let files = &[("test.cpp", "int a = 322;"), ("other.cpp", "int b = 644;")];
+
+    super::with_temporary_files(files, |d, fs| {
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(&fs[0]).parse().unwrap();
+        let file = tu.get_file(&fs[0]).unwrap();
+
+        assert!(file.path_equivalent(&fs[0]));
+        assert!(file.path_equivalent(d.join("..").join(d.file_name().unwrap()).join("test.cpp")));
+        assert!(!file.path_equivalent(&fs[1]));
+        assert!(!file.path_equivalent(d.join("missing.cpp")));
+
+        #[cfg(unix)]
+        {
+            let link = d.join("link.cpp");
+            std::os::unix::fs::symlink(&fs[0], &link).unwrap();
+            assert!(file.path_equivalent(&link));
+        }
+    });
+
     let source = "
         #if 0
         int skipped = 32;
@@ -80,6 +102,7 @@ pub fn test(clang: &Clang) {
         // This may fail, if clang internals DO have a source
         let file = child.get_location().unwrap().get_file_location().file;
         assert_eq!(file, None);
+        assert_eq!(child.get_location().unwrap().get_file(), None);
     });
 
 
@@ -132,12 +155,61 @@ pub fn test(clang: &Clang) {
         assert!(!location.is_in_system_header());
     });
 
+    super::with_file(&clang, "int a = 322;", |_, f| {
+        let entity = f.get_location(1, 5).get_entity().unwrap();
+        assert_eq!(entity.get_location().unwrap().get_file(), Some(f));
+    });
+
     // SourceRange _______________________________
 
+    let source = "int add(int left,  int right) {
+    /* Add. */ return left + right;
+}";
+
+    super::with_file(&clang, source, |_, f| {
+        let range = f.get_location(1, 5).get_entity().unwrap().get_range().unwrap();
+        assert_eq!(range.get_text(), Some(source.into()));
+        assert_eq!(range!(f, 1, 5, 1, 8).get_text(), Some("add".into()));
+    });
+
+    super::with_file(&clang, "int a = 1 +  2 * 3;", |_, f| {
+        let entity = f.get_location(1, 5).get_entity().unwrap();
+        let initializer = entity.get_children()[0];
+        let range = initializer.get_range().unwrap();
+        assert_eq!(range.get_contents(), Some("1 +  2 * 3".into()));
+        assert_eq!(range.get_contents(), range.get_text());
+    });
+
     super::with_file(&clang, "int a = 322;", |_, f| {
         let range = range!(f, 1, 5, 1, 6);
         assert_location_eq!(range.get_start().get_spelling_location(), Some(f), 1, 5, 4);
         assert_location_eq!(range.get_end().get_spelling_location(), Some(f), 1, 6, 5);
     });
 
+    super::with_file(&clang, "int a = 322;\nint b = 644;", |_, f| {
+        let a = range!(f, 1, 1, 1, 12);
+        let b = range!(f, 1, 5, 1, 6);
+        let c = range!(f, 1, 5, 1, 12);
+        let d = range!(f, 2, 1, 2, 12);
+
+        let mut ranges = vec![d, c, a, b];
+        ranges.sort_by(|l, r| l.cmp_by_file_offset(r));
+        assert_eq!(ranges, &[a, b, c, d]);
+        assert_eq!(a.cmp_by_file_offset(&a), Ordering::Equal);
+
+        let ranges = vec![d, c, a, b, a].into_iter().map(SourceRangeByFileOffset);
+        let mut ranges = ranges.collect::<Vec<_>>();
+        ranges.sort();
+        let ranges = ranges.into_iter().map(|r| r.0).collect::<Vec<_>>();
+        assert_eq!(ranges, &[a, a, b, c, d]);
+
+        let set = vec![d, c, a, b, a].into_iter().map(SourceRangeByFileOffset);
+        let set = set.collect::<BTreeSet<_>>();
+        let ranges = set.into_iter().map(|r| r.0).collect::<Vec<_>>();
+        assert_eq!(ranges, &[a, b, c, d]);
+
+        assert_eq!(a.len_bytes(), Some(11));
+        assert_eq!(b.len_bytes(), Some(1));
+        assert_eq!(d.len_bytes(), Some(11));
+    });
 }
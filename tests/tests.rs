@@ -116,10 +116,26 @@ mod sonar_test;
 
 #[test]
 fn test() {
+    assert!(Clang::is_available());
+    let clang = Clang::new().unwrap();
+    assert!(!Clang::is_available());
+    assert!(Clang::new().is_err());
+    drop(clang);
+    assert!(Clang::is_available());
+
+    mem::forget(Clang::new().unwrap());
+    assert!(!Clang::is_available());
+    unsafe { Clang::reset_availability(); }
+    assert!(Clang::is_available());
+
     let clang = Clang::new().unwrap();
 
     println!("libclang: {}", get_version());
 
+    if let Some(directory) = get_resource_dir() {
+        assert!(directory.join("include").is_dir());
+    }
+
     completion_test::test(&clang);
     diagnostic_test::test(&clang);
     documentation_test::test(&clang);
@@ -132,6 +148,38 @@ fn test() {
 
     assert_eq!(format!("{}", SourceError::Unknown), "an unknown error occurred");
 
+    // CompilationDatabase _______________________
+
+    with_temporary_directory(|d| {
+        let a = d.join("a.cpp");
+        let b = d.join("b.cpp");
+
+        let entry = |file: &Path, argument: &str| format!(
+            r#"{{ "directory": {:?}, "arguments": ["clang++", {:?}, "-c", {:?}], "file": {:?} }}"#,
+            d.to_str().unwrap(), argument, file.to_str().unwrap(), file.to_str().unwrap(),
+        );
+
+        let entries = [entry(&a, "-O0"), entry(&b, "-O0"), entry(&a, "-O2")];
+        let database = format!("[{}]", entries.join(", "));
+        fs::File::create(d.join("compile_commands.json")).unwrap()
+            .write_all(database.as_bytes()).unwrap();
+
+        let database = CompilationDatabase::from_directory(d).unwrap();
+        assert_eq!(database.get_files(), &[a.clone(), b.clone()]);
+
+        fs::File::create(&a).unwrap().write_all(b"int a;").unwrap();
+        fs::File::create(&b).unwrap().write_all(b"int b;").unwrap();
+
+        let index = Index::new(&clang, false, false);
+        let mut parsed = vec![];
+        database.parse_all(&index, |f, tu| {
+            let tu = tu.unwrap();
+            let children = tu.get_entity().get_children();
+            parsed.push((f.to_path_buf(), children.len()));
+        });
+        assert_eq!(parsed, &[(a.clone(), 1), (b, 1), (a, 1)]);
+    });
+
     // Entity ____________________________________
 
     let source = "
@@ -203,6 +251,8 @@ fn test() {
         assert_eq!(children[0].get_name(), Some("a".into()));
         assert_eq!(children[0].get_name_ranges(), &[range!(file, 1, 5, 1, 6)]);
         assert_eq!(children[0].get_range(), Some(range!(file, 1, 1, 1, 12)));
+        assert_eq!(children[0].get_start_location(), Some(file.get_location(1, 1)));
+        assert_eq!(children[0].get_end_location(), Some(file.get_location(1, 12)));
         assert_eq!(children[0].get_translation_unit().get_file(f), tu.get_file(f));
         assert_eq!(children[0].get_platform_availability(), Some(vec![]));
         assert_eq!(children[0].get_usr(), Some(Usr("c:@a".into())));
@@ -311,6 +361,175 @@ fn test() {
         assert!(children[2].is_definition());
     });
 
+    let source = "class A {\n    void a();\n};\nvoid A::a() { }";
+
+    with_translation_unit(&clang, "test.cpp", source, &[], |_, f, tu| {
+        let file = tu.get_file(f).unwrap();
+
+        let children = tu.get_entity().get_children();
+        assert_eq!(children.len(), 2);
+
+        let declaration = children[0].get_children()[0];
+        assert_eq!(declaration.get_range(), Some(range!(file, 2, 5, 2, 13)));
+        assert_eq!(declaration.get_definition_range(), Some(range!(file, 4, 1, 4, 16)));
+        assert_eq!(children[1].get_definition_range(), Some(range!(file, 4, 1, 4, 16)));
+    });
+
+    let source = "
+        constexpr int a() { return 322; }
+        constinit int b = 322;
+        int c = a();
+    ";
+
+    with_translation_unit(&clang, "test.cpp", source, &["-std=c++2a"], |_, _, tu| {
+        #[cfg(feature="clang_10_0")]
+        fn test_get_constexpr_kind(children: &[Entity]) {
+            assert_eq!(children.len(), 3);
+            assert_eq!(children[0].get_constexpr_kind(), Some(ConstexprKind::Constexpr));
+            assert!(children[0].is_constexpr());
+            assert_eq!(children[1].get_constexpr_kind(), Some(ConstexprKind::Constinit));
+            assert!(children[1].is_constexpr());
+            assert_eq!(children[2].get_constexpr_kind(), None);
+            assert!(!children[2].is_constexpr());
+        }
+
+        #[cfg(not(feature="clang_10_0"))]
+        fn test_get_constexpr_kind(_: &[Entity]) { }
+
+        test_get_constexpr_kind(&tu.get_entity().get_children()[..]);
+    });
+
+    let source = "/// Documented.\n__attribute__((deprecated)) int f();\nint g();";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 2);
+
+        let text = "/// Documented.\n__attribute__((deprecated)) int f()";
+        assert_eq!(children[0].get_full_text(), Some(text.into()));
+        assert_eq!(children[1].get_full_text(), Some("int g()".into()));
+    });
+
+    let source = "
+        void f(const char* format, ...) __attribute__((format(printf, 1, 2)));
+        __attribute__((__format__(__scanf__, 2, 0))) void g(int i, const char* format);
+        void h(const char* format, ...);
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 3);
+
+        assert_eq!(children[0].get_format_attribute(), Some(FormatAttribute {
+            archetype: "printf".into(), format_index: 1, first_to_check: 2
+        }));
+        assert_eq!(children[1].get_format_attribute(), Some(FormatAttribute {
+            archetype: "scanf".into(), format_index: 2, first_to_check: 0
+        }));
+        assert_eq!(children[2].get_format_attribute(), None);
+    });
+
+    let source = "
+        int f() __asm__(\"g\");
+        int h();
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 2);
+
+        assert_eq!(children[0].get_asm_label(), Some("g".into()));
+        assert_eq!(children[1].get_asm_label(), None);
+    });
+
+    let source = "
+        struct alignas(16) A { };
+        struct __attribute__((aligned(0x20))) B { };
+        struct __attribute__((aligned)) C { };
+        struct D { };
+    ";
+
+    with_entity(&clang, source, |e| {
+        #[cfg(feature="clang_9_0")]
+        fn test_get_alignment_attribute<'tu>(children: &[Entity<'tu>]) {
+            assert_eq!(children[0].get_alignment_attribute(), Some(16));
+            assert_eq!(children[1].get_alignment_attribute(), Some(32));
+            assert_eq!(children[2].get_alignment_attribute(), None);
+            assert_eq!(children[3].get_alignment_attribute(), None);
+        }
+
+        #[cfg(not(feature="clang_9_0"))]
+        fn test_get_alignment_attribute<'tu>(_: &[Entity<'tu>]) { }
+
+        let children = e.get_children();
+        assert_eq!(children.len(), 4);
+
+        test_get_alignment_attribute(&children);
+    });
+
+    let source = r#"
+        __attribute__((annotate("foo"), pure)) int f();
+        int g();
+    "#;
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 2);
+
+        let attributes = children[0].get_attributes();
+        let kinds = attributes.iter().map(|a| a.get_kind()).collect::<Vec<_>>();
+        assert!(kinds.contains(&EntityKind::AnnotateAttr));
+        assert!(kinds.contains(&EntityKind::PureAttr));
+        assert!(attributes.iter().all(|a| a.is_attribute()));
+
+        assert_eq!(children[1].get_attributes(), &[]);
+    });
+
+    let source = r#"
+        __attribute__((annotate("foo"), annotate("bar"))) int f();
+    "#;
+
+    with_entity(&clang, source, |e| {
+        let f = e.get_children()[0];
+        assert_eq!(f.get_annotate_value(), None);
+
+        let mut values = f.get_children().iter().filter_map(|c| {
+            c.get_annotate_value()
+        }).collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, &["bar", "foo"]);
+    });
+
+    with_entity(&clang, "struct A { }; union B { }; class C { }; int d;", |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 4);
+
+        assert_eq!(children[0].get_record_kind(), Some(RecordKind::Struct));
+        assert_eq!(children[1].get_record_kind(), Some(RecordKind::Union));
+        assert_eq!(children[2].get_record_kind(), Some(RecordKind::Class));
+        assert_eq!(children[3].get_record_kind(), None);
+    });
+
+    let source = "
+        extern int a;
+        int b;
+        void c();
+        void d() { }
+        void d();
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 5);
+
+        assert!(!children[0].has_definition_in_tu());
+        assert!(children[1].has_definition_in_tu());
+        assert!(!children[2].has_definition_in_tu());
+        assert!(children[3].has_definition_in_tu());
+        assert!(children[4].has_definition_in_tu());
+        assert!(!children[4].is_definition());
+    });
+
     let source = "
         struct A { struct { int b; }; int i : 322; };
     ";
@@ -374,6 +593,79 @@ fn test() {
         assert_eq!(children[1].get_comment_range(), Some(range!(file, 3, 9, 3, 39)));
     });
 
+    let source = "
+        /// First line.
+        ///   Indented line.
+        int a;
+
+        /**
+         * First line.
+         *
+         * Second paragraph.
+         */
+        int b;
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 2);
+
+        assert_eq!(children[0].get_comment_text(false), children[0].get_comment());
+        let text = "First line.\n  Indented line.";
+        assert_eq!(children[0].get_comment_text(true), Some(text.into()));
+
+        assert_eq!(children[1].get_comment_text(false), children[1].get_comment());
+        let text = "First line.\n\nSecond paragraph.";
+        assert_eq!(children[1].get_comment_text(true), Some(text.into()));
+    });
+
+    let source = "
+        int a; ///< The a.
+        int b; // The b.
+        int c;
+        /**< The d. */ int d;
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 4);
+
+        assert_eq!(children[0].get_trailing_comment(), Some("///< The a.".into()));
+        assert_eq!(children[1].get_trailing_comment(), None);
+        assert_eq!(children[2].get_trailing_comment(), None);
+        assert_eq!(children[3].get_trailing_comment(), None);
+    });
+
+    let source = "
+        // Leading a.
+        int a;
+        int b; // Trailing b.
+        /* Leading c. */ int c; /* Trailing c. */
+        int d;
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 4);
+
+        macro_rules! comments {
+            ($entity:expr, $leading:expr, $trailing:expr, $any:expr) => ({
+                let leading = $entity.get_associated_comment(CommentAttachment::Leading);
+                assert_eq!(leading, $leading.map(|s: &str| s.into()));
+                let trailing = $entity.get_associated_comment(CommentAttachment::Trailing);
+                assert_eq!(trailing, $trailing.map(|s: &str| s.into()));
+                let any = $entity.get_associated_comment(CommentAttachment::Any);
+                assert_eq!(any, $any.map(|s: &str| s.into()));
+            });
+        }
+
+        comments!(children[0], Some("// Leading a."), None, Some("// Leading a."));
+        comments!(children[1], None, Some("// Trailing b."), Some("// Trailing b."));
+        let (leading, trailing) = (Some("/* Leading c. */"), Some("/* Trailing c. */"));
+        comments!(children[2], leading, trailing, leading);
+        comments!(children[3], None, None, None);
+    });
+
     let source = "
         unsigned int integer = 322;
         enum A { B = 322, C = 644 };
@@ -400,6 +692,23 @@ fn test() {
         assert_eq!(children[1].get_enum_constant_value(), Some((644, 644)));
     });
 
+    let source = "
+        int integer = 322;
+        enum A { B = -322, C, D = 322 };
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 2);
+
+        assert_eq!(children[0].get_enum_constants(), None);
+        assert_eq!(children[1].get_enum_constants(), Some(vec![
+            EnumConstant { name: "B".into(), signed: -322, unsigned: -322i32 as u32 as u64 },
+            EnumConstant { name: "C".into(), signed: -321, unsigned: -321i32 as u32 as u64 },
+            EnumConstant { name: "D".into(), signed: 322, unsigned: 322 },
+        ]));
+    });
+
     let source = "
         void a();
         void b() throw();
@@ -423,6 +732,70 @@ fn test() {
         test_get_exception_specification(&children[..]);
     });
 
+    let source = "
+        struct A { };
+        namespace n { struct B { }; typedef A C; }
+        void f() throw(A, n::B, struct n::B, n::C, const char*, A&);
+        void g() throw(int, char);
+        namespace m { struct A { }; void h() throw(A); }
+        void j() throw(E);
+        struct E { };
+        void k() throw();
+        void l() noexcept;
+        void o();
+        void p(int i) throw(int);
+    ";
+
+    with_entity(&clang, source, |e| {
+        #[cfg(feature="clang_5_0")]
+        fn test_get_exception_types(children: &[Entity]) {
+            let names = |e: &Entity| {
+                e.get_exception_types().into_iter().map(|t| t.0).collect::<Vec<_>>()
+            };
+
+            let types = children[2].get_exception_types();
+            assert_eq!(names(&children[2]), &[
+                "A", "n::B", "struct n::B", "n::C", "const char *", "A &",
+            ]);
+            let a = children[0].get_type().unwrap();
+            assert_eq!(types[0].1, Some(a));
+            assert!(types[1].1.is_some());
+            assert_eq!(types[1].1, types[2].1);
+            assert_eq!(types[3].1.map(|t| t.get_canonical_type()), Some(a));
+
+            let specification = children[3].get_exception_specification();
+            assert_eq!(specification, Some(ExceptionSpecification::Dynamic));
+            assert_eq!(names(&children[3]), &["int", "char"]);
+            let types = children[3].get_exception_types();
+            assert!(types[0].1.map_or(true, |t| t.get_kind() == TypeKind::Int));
+            assert!(types[1].1.map_or(true, |t| {
+                t.get_kind() == TypeKind::CharS || t.get_kind() == TypeKind::CharU
+            }));
+
+            let m = children[4].get_children();
+            let types = m[1].get_exception_types();
+            assert_eq!(names(&m[1]), &["A"]);
+            assert_eq!(types[0].1, m[0].get_type());
+
+            assert_eq!(children[5].get_exception_types(), &[("E".into(), None)]);
+            assert_eq!(children[7].get_exception_types(), &[]);
+            assert_eq!(children[8].get_exception_types(), &[]);
+            assert_eq!(children[9].get_exception_types(), &[]);
+
+            let types = children[10].get_exception_types();
+            assert_eq!(names(&children[10]), &["int"]);
+            assert_eq!(types[0].1.map(|t| t.get_kind()), Some(TypeKind::Int));
+        }
+
+        #[cfg(not(feature="clang_5_0"))]
+        fn test_get_exception_types(_: &[Entity]) { }
+
+        let children = e.get_children();
+        assert_eq!(children.len(), 11);
+
+        test_get_exception_types(&children[..]);
+    });
+
     let files = &[
         ("test.hpp", ""),
         ("test.cpp", "#include \"test.hpp\""),
@@ -439,6 +812,101 @@ fn test() {
         assert_eq!(tu.get_file(&fs[1]).unwrap().get_includes(), &[last]);
     });
 
+    let files = &[
+        ("test.hpp", ""),
+        ("test.cpp", "#include \"./test.hpp\"\n#include <test.hpp>\nint a;"),
+    ];
+
+    with_temporary_files(files, |d, fs| {
+        let index = Index::new(&clang, false, false);
+        let mut parser = index.parser(&fs[1]);
+        let arguments = &[format!("-I{}", d.display())];
+        let tu = parser.arguments(arguments).detailed_preprocessing_record(true).parse().unwrap();
+
+        let children = tu.get_entity().get_children();
+        let inclusions = children.iter().filter(|e| {
+            e.get_kind() == EntityKind::InclusionDirective
+        }).collect::<Vec<_>>();
+        assert_eq!(inclusions.len(), 2);
+
+        assert_eq!(inclusions[0].get_inclusion_spelling(), Some("\"./test.hpp\"".into()));
+        assert_eq!(inclusions[1].get_inclusion_spelling(), Some("<test.hpp>".into()));
+
+        let path = fs::canonicalize(&fs[0]).unwrap();
+        for inclusion in &inclusions {
+            let resolved = inclusion.get_inclusion_resolved_path().unwrap();
+            assert_eq!(fs::canonicalize(resolved).unwrap(), path);
+        }
+
+        let last = children.iter().last().unwrap();
+        assert_eq!(last.get_inclusion_spelling(), None);
+        assert_eq!(last.get_inclusion_resolved_path(), None);
+    });
+
+    let files = &[
+        ("test.hpp", "int a;"),
+        ("test.cpp", "#include \"test.hpp\"\nint b;\nint c;"),
+    ];
+
+    with_temporary_files(files, |_, fs| {
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(&fs[1]).parse().unwrap();
+
+        let children = tu.get_entity().get_children();
+        assert_eq!(children.len(), 3);
+
+        assert!(children[1].is_in_same_file_as(&children[2]));
+        assert!(!children[0].is_in_same_file_as(&children[1]));
+        assert!(!tu.get_entity().is_in_same_file_as(&children[1]));
+    });
+
+    let source = "
+#define A 1
+#ifdef A
+#if A
+#elif 0
+#else
+#endif
+#endif
+#ifndef B
+#undef A
+#endif
+#pragma pack(1)
+int a = 322;
+    ";
+
+    with_temporary_file("test.cpp", source, |_, f| {
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(f).detailed_preprocessing_record(true).parse().unwrap();
+        let file = tu.get_file(f).unwrap();
+
+        let tokens = SourceRange::new(
+            file.get_location(1, 1), file.get_offset_location(source.len() as u32)
+        ).tokenize();
+        let mut entities = tu.annotate(&tokens).into_iter().filter_map(|e| e).filter(|e| {
+            e.is_preprocessing() && e.get_kind() != EntityKind::MacroExpansion
+        }).collect::<Vec<_>>();
+        entities.dedup();
+
+        let kinds = entities.iter().map(|e| e.get_preprocessing_directive_kind().unwrap());
+        assert_eq!(kinds.collect::<Vec<_>>(), &[
+            PreprocDirective::Define,
+            PreprocDirective::Ifdef,
+            PreprocDirective::If,
+            PreprocDirective::Elif,
+            PreprocDirective::Else,
+            PreprocDirective::Endif,
+            PreprocDirective::Endif,
+            PreprocDirective::Ifndef,
+            PreprocDirective::Undef,
+            PreprocDirective::Endif,
+            PreprocDirective::Pragma,
+        ]);
+
+        let declaration = tu.get_entity().get_children().into_iter().last().unwrap();
+        assert_eq!(declaration.get_preprocessing_directive_kind(), None);
+    });
+
     let source = "
         void a() { }
         class B { void b() { } };
@@ -531,78 +999,317 @@ fn test() {
     });
 
     let source = "
-        struct A { virtual void a() { } };
-        struct B : public A { virtual void a() { } };
+        void f(int i);
+        void f(float f);
+        void f(int i) { }
+        void g();
+        template <typename T> void f(T t);
     ";
 
     with_entity(&clang, source, |e| {
         let children = e.get_children();
-        assert_eq!(children.len(), 2);
-
-        assert_eq!(children[0].get_children()[0].get_overridden_methods(), None);
-        assert_eq!(children[1].get_children()[1].get_overridden_methods(), Some(vec![
-            children[0].get_children()[0]
-        ]));
+        assert_eq!(children.len(), 5);
+
+        let set = vec![children[0], children[1], children[4]];
+        assert_eq!(children[0].get_overload_set(), set);
+        assert_eq!(children[1].get_overload_set(), set);
+        assert_eq!(children[2].get_overload_set(), set);
+        assert_eq!(children[3].get_overload_set(), &[children[3]]);
+        assert_eq!(children[4].get_overload_set(), set);
+        assert_eq!(e.get_overload_set(), &[]);
     });
 
     let source = "
-        int integer = 322;
-        template <typename T, int I> void function() { }
-        template <> void function<int, 322>() { }
+        int x;
+        int& f();
+        int&& g();
+        int h();
+        void t() {
+            x; 1; \"s\"; f(); g(); h(); x = 1; x + 1; ++x; x++; *&x;
+            static_cast<int&&>(x); (int&)x; (x);
+        }
     ";
 
     with_entity(&clang, source, |e| {
-        #[cfg(feature="clang_3_6")]
-        fn test_get_template_arguments<'tu>(children: &[Entity<'tu>]) {
-            assert_eq!(children[0].get_template_arguments(), None);
-            assert_eq!(children[1].get_template_arguments(), None);
-            assert_eq!(children[2].get_template_arguments(), Some(vec![
-                TemplateArgument::Type(children[0].get_type().unwrap()),
-                TemplateArgument::Integral(322, 322),
-            ]));
-        }
+        let body = e.get_children()[4].get_children().into_iter().last().unwrap();
+        let categories = body.get_children().into_iter().map(|c| c.get_value_category());
+        assert_eq!(categories.collect::<Vec<_>>(), &[
+            Some(ValueCategory::LValue),
+            Some(ValueCategory::PRValue),
+            Some(ValueCategory::LValue),
+            Some(ValueCategory::LValue),
+            Some(ValueCategory::XValue),
+            Some(ValueCategory::PRValue),
+            Some(ValueCategory::LValue),
+            Some(ValueCategory::PRValue),
+            Some(ValueCategory::LValue),
+            Some(ValueCategory::PRValue),
+            Some(ValueCategory::LValue),
+            Some(ValueCategory::XValue),
+            Some(ValueCategory::LValue),
+            Some(ValueCategory::LValue),
+        ]);
+        assert_eq!(body.get_value_category(), None);
+    });
 
-        #[cfg(not(feature="clang_3_6"))]
-        fn test_get_template_arguments<'tu>(_: &[Entity<'tu>]) { }
+    let source = "
+        __builtin_va_list a;
+    ";
 
+    with_entity(&clang, source, |e| {
         let children = e.get_children();
-        assert_eq!(children.len(), 3);
+        assert_eq!(children.len(), 1);
 
-        assert_eq!(children[0].get_template(), None);
-        assert_eq!(children[0].get_template_kind(), None);
+        assert!(!e.is_builtin());
+        assert!(!children[0].is_builtin());
 
-        assert_eq!(children[1].get_template(), None);
-        assert_eq!(children[1].get_template_kind(), Some(EntityKind::FunctionDecl));
+        let declaration = children[0].get_type().unwrap().get_declaration().unwrap();
+        assert_eq!(declaration.get_name(), Some("__builtin_va_list".into()));
+        assert!(declaration.is_builtin());
+    });
 
-        assert_eq!(children[2].get_template(), Some(children[1]));
-        assert_eq!(children[2].get_template_kind(), None);
+    let source = "
+        class A { int a, b; void c(); void d(); void e(); };
+    ";
 
-        test_get_template_arguments(&children);
+    with_entity(&clang, source, |e| {
+        let children = e.get_children()[0].get_children_by_kind();
+        assert_eq!(children[&EntityKind::FieldDecl].len(), 2);
+        assert_eq!(children[&EntityKind::Method].len(), 3);
+        assert!(!children.contains_key(&EntityKind::Constructor));
+        assert_eq!(e.get_children_by_kind()[&EntityKind::ClassDecl].len(), 1);
     });
 
     let source = "
-        int integer = 322;
-        typedef int Integer;
+        class A { int a; void b(); int c; A(); void d(); };
     ";
 
     with_entity(&clang, source, |e| {
-        let children = e.get_children();
-        assert_eq!(children.len(), 2);
+        let class = e.get_children()[0];
+        let names = |es: Vec<Entity>| es.iter().map(|e| e.get_name().unwrap()).collect::<Vec<_>>();
 
-        assert_eq!(children[0].get_typedef_underlying_type(), None);
-        assert_eq!(children[1].get_typedef_underlying_type(), Some(children[0].get_type().unwrap()));
+        let fields = class.get_children_of_kind(EntityKind::FieldDecl);
+        assert_eq!(names(fields), &["a", "c"]);
+
+        let methods = class.get_children_matching(|k| {
+            k == EntityKind::Method || k == EntityKind::Constructor
+        });
+        assert_eq!(names(methods), &["b", "A", "d"]);
+
+        assert!(class.get_children_of_kind(EntityKind::Destructor).is_empty());
     });
 
-    let source = r#"
-        class A { };
-        class __attribute__((visibility("hidden"))) B { };
-    "#;
+    let source = "
+        typedef unsigned long size;
+        static size a;
+        const size* b;
+        template <typename T> inline size c(T t);
+        int d[3];
+    ";
 
     with_entity(&clang, source, |e| {
-        #[cfg(feature="clang_3_8")]
-        fn test_get_visibility<'tu>(children: &[Entity<'tu>]) {
-            assert_eq!(children[0].get_visibility(), Some(Visibility::Default));
-            assert_eq!(children[1].get_visibility(), Some(Visibility::Hidden));
+        let children = e.get_children();
+        assert_eq!(children.len(), 5);
+
+        let spellings = children.iter().map(|c| {
+            c.get_type_spelling_as_written()
+        }).collect::<Vec<_>>();
+        assert_eq!(spellings, &[
+            Some("unsigned long".into()),
+            Some("size".into()),
+            Some("const size*".into()),
+            Some("size".into()),
+            Some("int".into()),
+        ]);
+
+        let type_ = children[1].get_type().unwrap().get_canonical_type();
+        assert_eq!(type_.get_display_name(), "unsigned long");
+    });
+
+    let source = "
+        template <class T> void f(T&& t, const T&& u, T& v, int&& w);
+        template <class T> struct S { void g(T&& t); };
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 2);
+
+        let parameters = children[0].get_children().into_iter().filter(|c| {
+            c.get_kind() == EntityKind::ParmDecl
+        }).collect::<Vec<_>>();
+        assert_eq!(parameters.len(), 4);
+
+        let types = parameters.iter().map(|p| p.get_type().unwrap()).collect::<Vec<_>>();
+        assert!(types[0].is_rvalue_reference());
+        assert!(!types[0].is_lvalue_reference());
+        assert!(types[2].is_lvalue_reference());
+        assert!(!types[2].is_rvalue_reference());
+
+        assert!(parameters[0].is_forwarding_reference_parameter());
+        assert!(!parameters[1].is_forwarding_reference_parameter());
+        assert!(!parameters[2].is_forwarding_reference_parameter());
+        assert!(!parameters[3].is_forwarding_reference_parameter());
+
+        let method = children[1].get_children().into_iter().find(|c| {
+            c.get_kind() == EntityKind::Method
+        }).unwrap();
+        assert!(!method.get_children()[0].is_forwarding_reference_parameter());
+    });
+
+    let source = "
+        namespace a { namespace { struct B { int c; void d(); }; } }
+        void a::B::d() { }
+        int e;
+    ";
+
+    with_entity(&clang, source, |e| {
+        let mut entities = vec![];
+        e.visit_children(|c, _| {
+            entities.push(c);
+            EntityVisitResult::Recurse
+        });
+
+        let mut cache = QualifiedNameCache::new();
+        for entity in &entities {
+            assert_eq!(cache.name_of(entity), entity.get_qualified_name());
+        }
+
+        let names = entities.iter().filter(|e| e.is_declaration()).map(|e| cache.name_of(e));
+        assert_eq!(names.collect::<Vec<_>>(), &[
+            "a", "a", "a::B", "a::B::c", "a::B::d", "a::B::d", "e",
+        ]);
+    });
+
+    with_entity(&clang, "struct A { void a(); };\nvoid A::a() { }\nint b;", |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 3);
+
+        let declaration = children[0].get_children()[0];
+        assert_eq!(children[0].get_semantic_children(), &[declaration, children[1]]);
+        assert_eq!(e.get_semantic_children(), &[children[0], children[2]]);
+    });
+
+    let source = "
+        struct A {
+            A operator+(const A&) const;
+            void operator()();
+            int operator[](int);
+            void *operator new[](unsigned long);
+            operator int();
+            void operator_();
+        };
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children()[0].get_children();
+        assert_eq!(children.len(), 6);
+
+        let operators = children.iter().map(|c| c.get_overloaded_operator()).collect::<Vec<_>>();
+        assert_eq!(operators, &[
+            Some(OverloadedOperator::Plus),
+            Some(OverloadedOperator::Call),
+            Some(OverloadedOperator::Subscript),
+            Some(OverloadedOperator::ArrayNew),
+            None,
+            None,
+        ]);
+        assert_eq!(e.get_overloaded_operator(), None);
+    });
+
+    let source = "
+        struct A { virtual void a() { } };
+        struct B : public A { virtual void a() { } };
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 2);
+
+        assert_eq!(children[0].get_children()[0].get_overridden_methods(), None);
+        assert_eq!(children[1].get_children()[1].get_overridden_methods(), Some(vec![
+            children[0].get_children()[0]
+        ]));
+    });
+
+    let source = "
+        template <typename T> struct S { };
+        template <typename T> struct S<T*> { };
+        S<int> a;
+        S<int*> b;
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 4);
+
+        assert_eq!(children[0].get_instantiation_pattern(), None);
+        assert_eq!(children[1].get_instantiation_pattern(), None);
+
+        let a = children[2].get_type().unwrap().get_declaration().unwrap();
+        assert_eq!(a.get_instantiation_pattern(), Some(children[0]));
+        let b = children[3].get_type().unwrap().get_declaration().unwrap();
+        assert_eq!(b.get_instantiation_pattern(), Some(children[1]));
+    });
+
+    let source = "
+        int integer = 322;
+        template <typename T, int I> void function() { }
+        template <> void function<int, 322>() { }
+    ";
+
+    with_entity(&clang, source, |e| {
+        #[cfg(feature="clang_3_6")]
+        fn test_get_template_arguments<'tu>(children: &[Entity<'tu>]) {
+            assert_eq!(children[0].get_template_arguments(), None);
+            assert_eq!(children[1].get_template_arguments(), None);
+            assert_eq!(children[2].get_template_arguments(), Some(vec![
+                TemplateArgument::Type(children[0].get_type().unwrap()),
+                TemplateArgument::Integral(322, 322),
+            ]));
+        }
+
+        #[cfg(not(feature="clang_3_6"))]
+        fn test_get_template_arguments<'tu>(_: &[Entity<'tu>]) { }
+
+        let children = e.get_children();
+        assert_eq!(children.len(), 3);
+
+        assert_eq!(children[0].get_template(), None);
+        assert_eq!(children[0].get_template_kind(), None);
+
+        assert_eq!(children[1].get_template(), None);
+        assert_eq!(children[1].get_template_kind(), Some(EntityKind::FunctionDecl));
+
+        assert_eq!(children[2].get_template(), Some(children[1]));
+        assert_eq!(children[2].get_template_kind(), None);
+
+        test_get_template_arguments(&children);
+    });
+
+    let source = "
+        int integer = 322;
+        typedef int Integer;
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 2);
+
+        assert_eq!(children[0].get_typedef_underlying_type(), None);
+        assert_eq!(children[1].get_typedef_underlying_type(), Some(children[0].get_type().unwrap()));
+    });
+
+    let source = r#"
+        class A { };
+        class __attribute__((visibility("hidden"))) B { };
+    "#;
+
+    with_entity(&clang, source, |e| {
+        #[cfg(feature="clang_3_8")]
+        fn test_get_visibility<'tu>(children: &[Entity<'tu>]) {
+            assert_eq!(children[0].get_visibility(), Some(Visibility::Default));
+            assert_eq!(children[1].get_visibility(), Some(Visibility::Hidden));
         }
 
         #[cfg(not(feature="clang_3_8"))]
@@ -614,6 +1321,51 @@ fn test() {
         test_get_visibility(&children);
     });
 
+    let source = r#"
+        static void a() { }
+        void b() { }
+        __attribute__((visibility("hidden"))) void c() { }
+    "#;
+
+    with_entity(&clang, source, |e| {
+        #[cfg(feature="clang_3_8")]
+        fn test_get_effective_visibility<'tu>(children: &[Entity<'tu>]) {
+            assert_eq!(children[0].get_effective_visibility(), EffectiveVisibility::Local);
+            assert_eq!(children[1].get_effective_visibility(), EffectiveVisibility::Exported);
+            assert_eq!(children[2].get_effective_visibility(), EffectiveVisibility::Hidden);
+        }
+
+        #[cfg(not(feature="clang_3_8"))]
+        fn test_get_effective_visibility<'tu>(_: &[Entity<'tu>]) { }
+
+        let children = e.get_children();
+        assert_eq!(children.len(), 3);
+
+        test_get_effective_visibility(&children);
+    });
+
+    let source = "
+        __declspec(dllexport) void a() { }
+        __declspec(dllimport) void b();
+    ";
+
+    let arguments = &["-target", "x86_64-pc-windows-msvc", "-fms-extensions"];
+    with_translation_unit(&clang, "test.cpp", source, arguments, |_, _, tu| {
+        #[cfg(feature="clang_3_8")]
+        fn test_get_effective_visibility<'tu>(children: &[Entity<'tu>]) {
+            assert_eq!(children[0].get_effective_visibility(), EffectiveVisibility::Exported);
+            assert_eq!(children[1].get_effective_visibility(), EffectiveVisibility::Imported);
+        }
+
+        #[cfg(not(feature="clang_3_8"))]
+        fn test_get_effective_visibility<'tu>(_: &[Entity<'tu>]) { }
+
+        let children = tu.get_entity().get_children();
+        assert_eq!(children.len(), 2);
+
+        test_get_effective_visibility(&children);
+    });
+
     let source = "
         void a();
         [[noreturn]] void b();
@@ -635,6 +1387,28 @@ fn test() {
         test_attributes(&children);
     });
 
+    let source = "
+        void a();
+        [[noreturn]] void b();
+        void c() __attribute__((noreturn));
+        void d() { int noreturn = 0; }
+    ";
+
+    with_translation_unit(&clang, "test.cpp", source, &["--std=c++11"], |_, _, tu| {
+        let children = tu.get_entity().get_children();
+        assert_eq!(children.len(), 4);
+
+        assert!(!tu.get_entity().is_noreturn());
+        assert!(!children[0].is_noreturn());
+        assert!(children[1].is_noreturn());
+        assert!(children[2].is_noreturn());
+        assert!(!children[3].is_noreturn());
+    });
+
+    with_translation_unit(&clang, "test.c", "_Noreturn void a(void);", &[], |_, _, tu| {
+        assert!(tu.get_entity().get_children()[0].is_noreturn());
+    });
+
     let source = "
         class Class {
             void a() const { }
@@ -681,9 +1455,41 @@ fn test() {
         let children = e.get_children();
         assert_eq!(children.len(), 2);
 
+        assert!(!children[0].enum_is_scoped());
+        assert!(children[1].enum_is_scoped());
+
         test_is_scoped(&children[..]);
     });
 
+    let source = "
+        int integer = 322;
+        enum A { A_A = 322 };
+        enum class B : short { B_A };
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 3);
+
+        assert_eq!(children[0].get_enum_info(), None);
+
+        let info = children[1].get_enum_info().unwrap();
+        assert_eq!(info.name, Some("A".into()));
+        assert!(!info.is_scoped);
+        assert_eq!(info.underlying_type.get_kind(), TypeKind::UInt);
+        assert_eq!(info.constants, &[
+            EnumConstant { name: "A_A".into(), signed: 322, unsigned: 322 },
+        ]);
+
+        let info = children[2].get_enum_info().unwrap();
+        assert_eq!(info.name, Some("B".into()));
+        assert!(info.is_scoped);
+        assert_eq!(info.underlying_type.get_kind(), TypeKind::Short);
+        assert_eq!(info.constants, &[
+            EnumConstant { name: "B_A".into(), signed: 0, unsigned: 0 },
+        ]);
+    });
+
     let source = "
         class Class {
             Class(int) { }
@@ -721,6 +1527,29 @@ fn test() {
         test_constructors(&children);
     });
 
+    let source = "
+        class A { ~A(); };
+        class B { int b; };
+        class C { ~C() = default; };
+    ";
+
+    with_entity(&clang, source, |e| {
+        #[cfg(feature="clang_3_9")]
+        fn test_has_user_declared_destructor<'tu>(children: &[Entity<'tu>]) {
+            assert!(children[0].has_user_declared_destructor());
+            assert!(!children[1].has_user_declared_destructor());
+            assert!(!children[2].has_user_declared_destructor());
+        }
+
+        #[cfg(not(feature="clang_3_9"))]
+        fn test_has_user_declared_destructor<'tu>(_: &[Entity<'tu>]) { }
+
+        let children = e.get_children();
+        assert_eq!(children.len(), 3);
+
+        test_has_user_declared_destructor(&children);
+    });
+
     let source = "
         struct A {
             void a() { }
@@ -868,6 +1697,68 @@ fn test() {
         test_get_mangled_objc_names(&entities[1]);
     });
 
+    let source = "
+        @protocol P
+        @end
+        @interface A
+        @end
+        @interface B : A <P>
+        @end
+    ";
+
+    with_translation_unit(&clang, "test.m", source, &[], |_, _, tu| {
+        let entities = tu.get_entity().get_children();
+        assert_eq!(entities.len(), 4);
+
+        assert_eq!(entities[2].get_objc_protocols(), &[]);
+        assert_eq!(entities[2].get_objc_superclass(), None);
+        assert_eq!(entities[3].get_objc_protocols(), &[entities[1]]);
+        assert_eq!(entities[3].get_objc_superclass(), Some(entities[2]));
+    });
+
+    let source = "
+        @interface A
+        @end
+        @interface A (Extras)
+        @end
+    ";
+
+    with_translation_unit(&clang, "test.m", source, &[], |_, _, tu| {
+        let entities = tu.get_entity().get_children();
+        assert_eq!(entities.len(), 3);
+
+        assert_eq!(entities[1].get_objc_category_class(), None);
+        assert_eq!(entities[2].get_kind(), EntityKind::ObjCCategoryDecl);
+        assert_eq!(entities[2].get_objc_category_class(), Some(entities[1]));
+        assert_eq!(entities[2].get_objc_category_class().unwrap().get_name(), Some("A".into()));
+    });
+
+    let source = "
+        @interface A
+        @end
+        @implementation A
+        @end
+        @interface B
+        @end
+    ";
+
+    with_translation_unit(&clang, "test.m", source, &[], |_, _, tu| {
+        let entities = tu.get_entity().get_children();
+        let find = |kind, name: &str| entities.iter().find(|e| {
+            e.get_kind() == kind && e.get_name() == Some(name.into())
+        }).cloned();
+
+        let interface = find(EntityKind::ObjCInterfaceDecl, "A").unwrap();
+        let implementation = find(EntityKind::ObjCImplementationDecl, "A").unwrap();
+        assert_eq!(interface.get_objc_implementation(), Some(implementation));
+        assert_eq!(interface.get_objc_interface(), None);
+        assert_eq!(implementation.get_objc_interface(), Some(interface));
+        assert_eq!(implementation.get_objc_implementation(), None);
+
+        let interface = find(EntityKind::ObjCInterfaceDecl, "B").unwrap();
+        assert_eq!(interface.get_objc_implementation(), None);
+    });
+
     let source = "
         struct x {
             char y;
@@ -977,44 +1868,697 @@ fn test() {
         test_get_objc_getter_setter_name(&children[1].get_children());
     });
 
-    // Index _____________________________________
+    let source = "
+        int a = 322;
+        void f(int a, double b = 1.0);
+    ";
 
-    let mut index = Index::new(&clang, false, false);
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 2);
 
-    let mut options = ThreadOptions::default();
-    assert_eq!(index.get_thread_options(), options);
+        assert_eq!(children[0].get_parameters(), None);
 
-    options.editing = true;
-    index.set_thread_options(options);
-    assert_eq!(index.get_thread_options(), options);
+        let parameters = children[1].get_parameters().unwrap();
+        assert_eq!(parameters.len(), 2);
 
-    // TranslationUnit ___________________________
+        assert_eq!(parameters[0].name, Some("a".into()));
+        assert_eq!(parameters[0].type_, children[0].get_type().unwrap());
+        assert_eq!(parameters[0].default_argument, None);
+        assert_eq!(parameters[0].index, 0);
 
-    with_translation_unit(&clang, "test.cpp", "int a = 322;", &[], |d, _, tu| {
-        let file = d.join("test.cpp.gch");
-        tu.save(&file).unwrap();
-        let index = Index::new(&clang, false, false);
-        let _ = TranslationUnit::from_ast(&index, &file).unwrap();
+        assert_eq!(parameters[1].name, Some("b".into()));
+        assert_eq!(parameters[1].type_.get_kind(), TypeKind::Double);
+        assert_eq!(parameters[1].default_argument.unwrap().get_kind(), EntityKind::FloatingLiteral);
+        assert_eq!(parameters[1].index, 1);
     });
 
-    with_temporary_file("test.cpp", "int a = 322;", |_, f| {
-        let index = Index::new(&clang, false, false);
-        let _ = index.parser(f).unsaved(&[Unsaved::new(f, "int a = 644;")]).parse().unwrap();
-    });
+    let source = "
+        struct A { void a() { } };
+        A a;
+        void f() { int x; struct L { } l; }
+    ";
 
-    with_translation_unit(&clang, "test.cpp", "int a = 322;", &[], |d, _, tu| {
-        assert_eq!(tu.get_file(d.join("test.c")), None);
-    });
+    with_entity(&clang, source, |e| {
+        assert!(!e.is_implicit());
 
-    with_translation_unit(&clang, "test.cpp", "int a = 322;", &[], |_, _, tu| {
-        let usage = tu.get_memory_usage();
-        assert_eq!(usage.get(&MemoryUsage::Selectors), Some(&0));
+        let children = e.get_children();
+        assert_eq!(children.len(), 3);
+
+        assert!(!children[0].is_implicit());
+        assert!(!children[0].get_children()[0].is_implicit());
+        assert!(!children[1].is_implicit());
+        assert!(!children[2].is_implicit());
+
+        let mut locals = vec![];
+        children[2].visit_children(|c, _| {
+            if c.is_declaration() {
+                locals.push(c);
+            }
+            EntityVisitResult::Recurse
+        });
+        let names = locals.iter().map(|l| l.get_name().unwrap()).collect::<Vec<_>>();
+        assert!(names.contains(&"x".into()) && names.contains(&"L".into()));
+        assert!(locals.iter().all(|l| !l.is_implicit()));
+
+        let construct = children[1].get_children().into_iter().find(|c| {
+            c.get_kind() == EntityKind::CallExpr
+        }).unwrap();
+
+        let constructor = construct.get_reference().unwrap();
+        assert_eq!(constructor.get_kind(), EntityKind::Constructor);
+        assert!(constructor.is_implicit());
+    });
+
+    let files = &[
+        ("test.hpp", "struct H { int h; };"),
+        ("test.cpp", "#include \"test.hpp\"\nH h;"),
+    ];
+
+    with_temporary_files(files, |_, fs| {
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(&fs[1]).parse().unwrap();
+
+        let children = tu.get_entity().get_children();
+        let header = children.iter().filter(|c| c.get_location().map_or(false, |l| {
+            l.get_file_location().file == tu.get_file(&fs[0])
+        })).collect::<Vec<_>>();
+        assert_eq!(header.len(), 1);
+        assert!(!header[0].is_implicit());
+        assert!(!header[0].get_children()[0].is_implicit());
+
+        let variable = children.iter().find(|c| c.get_name() == Some("h".into())).unwrap();
+        let construct = variable.get_children().into_iter().find(|c| {
+            c.get_kind() == EntityKind::CallExpr
+        }).unwrap();
+
+        let constructor = construct.get_reference().unwrap();
+        assert_eq!(constructor.get_kind(), EntityKind::Constructor);
+        let location = constructor.get_location().unwrap().get_file_location();
+        assert_eq!(location.file, tu.get_file(&fs[0]));
+        assert!(constructor.is_implicit());
+    });
+
+    let source = "
+        int a = 322;
+        int b = a + a;
+        int c = b;
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 3);
+
+        let references = children[0].get_references();
+        assert_eq!(references.len(), 2);
+        for reference in references {
+            assert_eq!(reference.get_kind(), EntityKind::DeclRefExpr);
+            assert_eq!(reference.get_reference(), Some(children[0]));
+        }
+
+        assert_eq!(children[1].get_references().len(), 1);
+        assert_eq!(children[2].get_references(), &[]);
+    });
+
+    let source = r#"
+        static_assert(sizeof(int) == 4, "bad int");
+    "#;
+
+    with_entity(&clang, source, |e| {
+        #[cfg(feature="clang_3_9")]
+        fn test_get_static_assert(children: &[Entity]) {
+            let assertion = children[0].get_static_assert().unwrap();
+            assert_eq!(assertion.condition.get_kind(), EntityKind::BinaryOperator);
+            assert_eq!(assertion.message, Some("bad int".into()));
+        }
+
+        #[cfg(not(feature="clang_3_9"))]
+        fn test_get_static_assert(_: &[Entity]) { }
+
+        let children = e.get_children();
+        assert_eq!(children.len(), 1);
+
+        test_get_static_assert(&children[..]);
+    });
+
+    let source = "
+        struct P { int x; int y; int z[4]; };
+        struct P p = { .x = 1, .y = 2, .z[3] = 3 };
+        struct P q = { 1, 2 };
+    ";
+
+    with_translation_unit(&clang, "test.c", source, &[], |_, _, tu| {
+        let get_designators = |e: &Entity| {
+            let list = e.get_children().into_iter().find(|c| {
+                c.get_kind() == EntityKind::InitListExpr
+            }).unwrap();
+            list.get_children().iter().map(|c| c.get_designators()).collect::<Vec<_>>()
+        };
+
+        let children = tu.get_entity().get_children();
+        assert_eq!(children.len(), 3);
+
+        assert_eq!(get_designators(&children[1]), &[
+            Some(vec![Designator::Field("x".into())]),
+            Some(vec![Designator::Field("y".into())]),
+            Some(vec![Designator::Field("z".into()), Designator::ArrayIndex(3)]),
+        ]);
+
+        assert_eq!(get_designators(&children[2]), &[None, None]);
+    });
+
+    let source = "
+        class A { void a() { int x = 0; } int b; };
+        class B { void c() { int y = 0; } };
+    ";
+
+    with_entity(&clang, source, |e| {
+        let mut names = vec![];
+        e.visit_children_bounded(
+            |e| e.get_kind() == EntityKind::ClassDecl && e.get_name() == Some("A".into()),
+            |e| names.push(e.get_name()),
+        );
+        let expected = vec![
+            Some("A".into()), Some("a".into()), Some("b".into()), Some("B".into()),
+        ];
+        assert_eq!(names, expected);
+    });
+
+    with_entity(&clang, source, |e| {
+        let mut visited = vec![];
+        e.visit_children(|e, _| {
+            visited.push(e);
+            EntityVisitResult::Recurse
+        });
+
+        let mut walker = ChildrenWalker::new(&e);
+        assert_eq!(walker.next(), Some(visited[0]));
+        assert_eq!(walker.collect::<Vec<_>>(), &visited[1..]);
+    });
+
+    let source = "
+        void f(int a) {
+            int b = a;
+            struct C { int d; };
+            for (int e = 0; e < b; ++e) {
+                typedef int F;
+                F g = e;
+            }
+        }
+    ";
+
+    with_entity(&clang, source, |e| {
+        let function = e.get_children()[0];
+        let declarations = function.get_local_declarations().iter().map(|d| {
+            (d.get_kind(), d.get_name().unwrap())
+        }).collect::<Vec<_>>();
+        assert_eq!(declarations, &[
+            (EntityKind::VarDecl, "b".into()),
+            (EntityKind::StructDecl, "C".into()),
+            (EntityKind::VarDecl, "e".into()),
+            (EntityKind::TypedefDecl, "F".into()),
+            (EntityKind::VarDecl, "g".into()),
+        ]);
+    });
+
+    let source = "
+        class A { virtual void a(); virtual void b(); virtual void c() const; void d(); };
+        class B : public A { void b() override; virtual void e(); virtual ~B(); };
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 2);
+
+        let indices = |class: &Entity| class.get_children().iter().filter(|c| {
+            c.get_kind() != EntityKind::BaseSpecifier
+        }).map(|c| c.get_virtual_method_index()).collect::<Vec<_>>();
+        assert_eq!(indices(&children[0]), &[Some(0), Some(1), Some(2), None]);
+        assert_eq!(indices(&children[1]), &[Some(1), Some(3), Some(4)]);
+    });
+
+    let source = "
+        int a = 322;
+        int b = a;
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 2);
+
+        let mut reference = None;
+        children[1].visit_children(|c, _| {
+            if c.get_kind() == EntityKind::DeclRefExpr {
+                reference = Some(c);
+                EntityVisitResult::Break
+            } else {
+                EntityVisitResult::Recurse
+            }
+        });
+
+        let (declaration, type_) = reference.unwrap().resolve_reference().unwrap();
+        assert_eq!(declaration, children[0]);
+        assert_eq!(declaration.get_kind(), EntityKind::VarDecl);
+        assert_eq!(type_.map(|t| t.get_kind()), Some(TypeKind::Int));
+    });
+
+    let source = "namespace A { namespace B { int c; } }\nint d = A::B::c;";
+
+    with_translation_unit(&clang, "test.cpp", source, &[], |_, f, tu| {
+        let file = tu.get_file(f).unwrap();
+
+        let mut reference = None;
+        tu.get_entity().visit_children(|c, _| {
+            if c.get_kind() == EntityKind::DeclRefExpr {
+                reference = Some(c);
+                EntityVisitResult::Break
+            } else {
+                EntityVisitResult::Recurse
+            }
+        });
+
+        let reference = reference.unwrap();
+        let ranges = reference.get_reference_name_ranges(false, false, false);
+        assert_eq!(ranges, &[range!(file, 2, 15, 2, 16)]);
+        let ranges = reference.get_reference_name_ranges(true, false, false);
+        assert_eq!(ranges, &[range!(file, 2, 9, 2, 16)]);
+    });
+
+    // Index _____________________________________
+
+    let mut index = Index::new(&clang, false, false);
+
+    let mut options = ThreadOptions::default();
+    assert_eq!(index.get_thread_options(), options);
+
+    options.editing = true;
+    index.set_thread_options(options);
+    assert_eq!(index.get_thread_options(), options);
+
+    let options = ThreadOptions::all();
+    assert_eq!(options, ThreadOptions { editing: true, indexing: true });
+    index.set_thread_options(options);
+    assert_eq!(index.get_thread_options(), options);
+
+    let mut options = IndexOptions::default();
+    assert_eq!(index.get_index_options(), options);
+
+    options.suppress_warnings = true;
+    options.skip_parsed_bodies_in_session = true;
+    index.set_index_options(options);
+    assert_eq!(index.get_index_options(), options);
+
+    with_temporary_file("test.cpp", "int a = 322;", |_, f| {
+        let index = Index::with_options(&clang, options);
+        assert_eq!(index.get_index_options(), options);
+        let tu = index.parser(f).parse().unwrap();
+        assert_eq!(tu.get_entity().get_children().len(), 1);
+    });
+
+    let source = "
+        struct A { };
+        void f() { A a; }
+    ";
+
+    with_temporary_file("test.cpp", source, |_, f| {
+        let index = |options| {
+            let index = Index::with_options(&clang, options);
+            let tu = index.parser(f).parse().unwrap();
+            let mut declarations = vec![];
+            let mut references = vec![];
+            assert!(index.index_translation_unit(&tu, |e, r| {
+                match r {
+                    Some(r) => references.push((e.get_kind(), r.get_name())),
+                    None => declarations.push(e.get_name().unwrap()),
+                }
+            }));
+            (declarations, references)
+        };
+
+        let (declarations, references) = index(IndexOptions::default());
+        assert_eq!(declarations, &["A", "f"]);
+        assert_eq!(references, &[(EntityKind::TypeRef, Some("A".into()))]);
+
+        let options = IndexOptions {
+            index_function_local_symbols: true, .. IndexOptions::default()
+        };
+        let (declarations, references) = index(options);
+        assert_eq!(declarations, &["A", "f", "a"]);
+        assert_eq!(references, &[(EntityKind::TypeRef, Some("A".into()))]);
+    });
+
+    // TranslationUnit ___________________________
+
+    with_translation_unit(&clang, "test.cpp", "int a = 322;", &[], |d, _, tu| {
+        let file = d.join("test.cpp.gch");
+        tu.save(&file).unwrap();
+        let index = Index::new(&clang, false, false);
+        let _ = TranslationUnit::from_ast(&index, &file).unwrap();
+    });
+
+    with_translation_unit(&clang, "test.cpp", "int a = 322;", &[], |d, _, tu| {
+        let file = d.join("test.cpp.gch");
+        tu.save(&file).unwrap();
+        let bytes = fs::read(&file).unwrap();
+        let index = Index::new(&clang, false, false);
+        let tu = TranslationUnit::from_ast_bytes(&index, &bytes).unwrap();
+        assert_eq!(tu.get_entity().get_children()[0].get_name(), Some("a".into()));
+    });
+
+    with_temporary_file("test.cpp", "int a = 322;", |_, f| {
+        let index = Index::new(&clang, false, false);
+        let _ = index.parser(f).unsaved(&[Unsaved::new(f, "int a = 644;")]).parse().unwrap();
+    });
+
+    let tu = parse_cpp(&clang, "int x = 1;").unwrap();
+    let children = tu.get().get_entity().get_children();
+    assert_eq!(children.len(), 1);
+    assert_eq!(children[0].get_kind(), EntityKind::VarDecl);
+    assert_eq!(children[0].get_name(), Some("x".into()));
+
+    let source = "
+#if 0
+int a;
+#elif 1
+int b;
+#else
+int c;
+#endif
+#ifdef A
+#if 0
+int d;
+#endif
+#else
+int e;
+#endif
+#if B > 1
+int f;
+#endif
+    ";
+
+    with_temporary_file("test.cpp", source, |_, f| {
+        #[cfg(feature="clang_10_0")]
+        fn test_get_excluded_blocks(clang: &Clang, f: &Path) {
+            let index = Index::new(clang, false, false);
+            let mut parser = index.parser(f);
+            parser.detailed_preprocessing_record(true).retain_excluded_conditional_blocks(true);
+            let tu = parser.parse().unwrap();
+            let file = tu.get_file(f).unwrap();
+            assert_eq!(tu.get_excluded_blocks(), &[
+                range!(file, 2, 1, 4, 6),
+                range!(file, 6, 1, 8, 7),
+                range!(file, 10, 1, 12, 7),
+            ]);
+
+            parser.retain_excluded_conditional_blocks(false);
+            let tu = parser.parse().unwrap();
+            assert_eq!(tu.get_excluded_blocks(), &[]);
+        }
+
+        #[cfg(not(feature="clang_10_0"))]
+        fn test_get_excluded_blocks(_: &Clang, _: &Path) { }
+
+        test_get_excluded_blocks(&clang, f);
+    });
+
+    let source = "
+        namespace a { int b; }
+        namespace c { namespace d { } }
+        namespace a { int e; }
+    ";
+
+    with_translation_unit(&clang, "test.cpp", source, &[], |_, _, tu| {
+        let children = tu.get_entity().get_children();
+        assert_eq!(children.len(), 3);
+
+        let d = children[1].get_children()[0];
+        assert_eq!(tu.get_namespace_declarations(), &[children[0], children[1], d, children[2]]);
+        assert_eq!(tu.get_namespaces(), &[children[0], children[1], d]);
+    });
+
+    let mut snapshot = None;
+    with_translation_unit(&clang, "test.cpp", "struct A { int a; };\nint b;", &[], |_, _, tu| {
+        snapshot = Some(tu.snapshot());
+    });
+
+    let snapshot = std::thread::spawn(move || snapshot.unwrap()).join().unwrap();
+    assert_eq!(snapshot.kind, EntityKind::TranslationUnit);
+    assert_eq!(snapshot.children.len(), 2);
+
+    let a = &snapshot.children[0];
+    assert_eq!(a.kind, EntityKind::StructDecl);
+    assert_eq!(a.name, Some("A".into()));
+    assert_eq!(a.usr, Some(Usr("c:@S@A".into())));
+    assert_eq!(a.location.as_ref().map(|l| (l.1, l.2)), Some((1, 8)));
+    assert_eq!(a.type_, Some("A".into()));
+    assert_eq!(a.children.len(), 1);
+    assert_eq!(a.children[0].kind, EntityKind::FieldDecl);
+    assert_eq!(a.children[0].type_, Some("int".into()));
+
+    let b = &snapshot.children[1];
+    assert_eq!(b.kind, EntityKind::VarDecl);
+    assert_eq!(b.location.as_ref().map(|l| (l.1, l.2)), Some((2, 5)));
+    assert!(b.children.is_empty());
+
+    let source = "
+        struct A;
+        struct A { int a; };
+        struct A;
+        void f();
+        void f() { }
+        extern int b;
+    ";
+
+    with_translation_unit(&clang, "test.cpp", source, &[], |_, _, tu| {
+        let children = tu.get_entity().get_children();
+        assert_eq!(children.len(), 6);
+
+        let symbols = SymbolTable::new(&tu);
+        assert_eq!(symbols.len(), 4);
+        assert_eq!(symbols.iter().count(), 4);
+        assert!(!symbols.is_empty());
+
+        let lookup = |usr: &str| symbols.lookup(&Usr(usr.into()));
+        assert_eq!(lookup("c:@S@A"), Some(children[1]));
+        assert_eq!(lookup("c:@S@A@FI@a"), Some(children[1].get_children()[0]));
+        assert_eq!(lookup("c:@F@f#"), Some(children[4]));
+        assert_eq!(lookup("c:@b"), Some(children[5]));
+        assert_eq!(lookup("c:@S@B"), None);
+    });
+
+    let files = &[
+        ("a.h", "#pragma once\nint a;"),
+        ("test.cpp", "#include \"a.h\"\n#pragma pack(1)\nstruct S { char c; int i; };"),
+    ];
+
+    with_temporary_files(files, |_, fs| {
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(&fs[1]).parse().unwrap();
+        let a = tu.get_file(&fs[0]).unwrap();
+        let test = tu.get_file(&fs[1]).unwrap();
+
+        let pragmas = tu.get_pragmas();
+        assert_eq!(pragmas.len(), 2);
+        assert_eq!(pragmas[0].text, "pack(1)");
+        assert_eq!(pragmas[0].range, range!(test, 2, 1, 2, 16));
+        assert_eq!(pragmas[1].text, "once");
+        assert_eq!(pragmas[1].range, range!(a, 1, 1, 1, 13));
+    });
+
+    let source = r#"
+        const char* a = "a" "b\n";
+        const char* b = "\x41\102é";
+    "#;
+
+    with_translation_unit(&clang, "test.c", source, &[], |_, _, tu| {
+        let literals = tu.get_string_literals();
+        assert_eq!(literals.len(), 2);
+        assert!(literals.iter().all(|&(e, _)| e.get_kind() == EntityKind::StringLiteral));
+        assert_eq!(literals[0].1, "ab\n");
+        assert_eq!(literals[1].1, "AB\u{e9}");
+    });
+
+    let source = "int f(int a) {\n    return a  + 1;\n}";
+
+    with_translation_unit(&clang, "test.cpp", source, &[], |_, f, tu| {
+        let file = tu.get_file(f).unwrap();
+
+        let kind = |l, c| tu.get_enclosing_entity(&file.get_location(l, c)).map(|e| e.get_kind());
+        assert_eq!(kind(2, 5), Some(EntityKind::ReturnStmt));
+        assert_eq!(kind(2, 12), Some(EntityKind::DeclRefExpr));
+        assert_eq!(kind(2, 14), Some(EntityKind::BinaryOperator));
+        assert_eq!(kind(2, 17), Some(EntityKind::IntegerLiteral));
+        assert_eq!(kind(1, 1), Some(EntityKind::FunctionDecl));
+    });
+
+    let files = &[
+        ("a.hpp", "#include \"b.hpp\""),
+        ("b.hpp", "#include \"c.hpp\""),
+        ("c.hpp", ""),
+        ("test.cpp", "#include \"a.hpp\"\n#include \"c.hpp\""),
+    ];
+
+    with_temporary_files(files, |_, fs| {
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(&fs[3]).parse().unwrap();
+
+        for system in &[false, true] {
+            let mut dependencies = tu.get_dependencies(*system);
+            dependencies.sort();
+            assert_eq!(dependencies, &fs[..3]);
+        }
+    });
+
+    let files = &[
+        ("a.hpp", "#include \"b.hpp\""),
+        ("b.hpp", ""),
+        ("test.cpp", "#include \"a.hpp\""),
+    ];
+
+    with_temporary_files(files, |_, fs| {
+        let index = Index::new(&clang, false, false);
+        let tu = index.parser(&fs[2]).parse().unwrap();
+        let a = tu.get_file(&fs[0]).unwrap();
+        let b = tu.get_file(&fs[1]).unwrap();
+        let test = tu.get_file(&fs[2]).unwrap();
+
+        let mut inclusions = vec![];
+        tu.visit_inclusions(|f, stack| {
+            let stack = stack.iter().map(|l| {
+                let location = l.get_file_location();
+                (location.file.unwrap(), location.line)
+            }).collect::<Vec<_>>();
+            inclusions.push((f, stack));
+        });
+        inclusions.sort_by_key(|i| i.1.len());
+
+        assert_eq!(inclusions, &[
+            (test, vec![]),
+            (a, vec![(test, 1)]),
+            (b, vec![(a, 1), (test, 1)]),
+        ]);
+    });
+
+    let files = &[
+        ("header.h", "/// Documented.\nint f();"),
+        ("test.cpp", "#include <header.h>"),
+    ];
+
+    with_temporary_files(files, |d, fs| {
+        let index = Index::new(&clang, false, false);
+        let arguments = &["-isystem", d.to_str().unwrap()];
+
+        let tu = index.parser(&fs[1]).arguments(arguments).parse().unwrap();
+        let function = tu.get_entity().get_children().into_iter().last().unwrap();
+        assert_eq!(function.get_name(), Some("f".into()));
+        assert_eq!(function.get_comment(), None);
+
+        let mut parser = index.parser(&fs[1]);
+        let tu = parser.arguments(arguments).retain_comments_from_system_headers().parse().unwrap();
+        let function = tu.get_entity().get_children().into_iter().last().unwrap();
+        assert_eq!(function.get_name(), Some("f".into()));
+        assert_eq!(function.get_comment(), Some("/// Documented.".into()));
+    });
+
+    with_temporary_file("test.c", "void f(int* _Nonnull p);", |_, f| {
+        #[cfg(feature="clang_8_0")]
+        fn test_full_attribute_info(index: &Index, f: &Path) {
+            let tu = index.parser(f).full_attribute_info().parse().unwrap();
+            let function = tu.get_entity().get_children()[0];
+            let parameter = function.get_arguments().unwrap()[0];
+            assert_eq!(parameter.get_type().unwrap().get_kind(), TypeKind::Attributed);
+        }
+
+        #[cfg(not(feature="clang_8_0"))]
+        fn test_full_attribute_info(_: &Index, _: &Path) { }
+
+        let index = Index::new(&clang, false, false);
+        test_full_attribute_info(&index, f);
+    });
+
+    with_temporary_file("test.cpp", "int a = 322;", |_, f| {
+        #[cfg(feature="clang_5_0")]
+        fn test_target(index: &Index, f: &Path) {
+            let tu = index.parser(f).target("i686-unknown-linux-gnu").parse().unwrap();
+            let target = tu.get_target();
+            assert_eq!(target.triple, "i686-unknown-linux-gnu");
+            assert_eq!(target.pointer_width, 32);
+        }
+
+        #[cfg(not(feature="clang_5_0"))]
+        fn test_target(_: &Index, _: &Path) { }
+
+        let index = Index::new(&clang, false, false);
+        test_target(&index, f);
+    });
+
+    let files = &[("prelude.h", "#define VALUE 322"), ("main.c", "int a = VALUE;")];
+
+    with_temporary_files(files, |_, fs| {
+        let index = Index::new(&clang, false, false);
+
+        let tu = index.parser(&fs[1]).parse().unwrap();
+        assert_eq!(tu.get_diagnostics().len(), 1);
+
+        let tu = index.parser(&fs[1]).force_include(&fs[0]).parse().unwrap();
+        assert!(tu.get_diagnostics().is_empty());
+        let children = tu.get_entity().get_children();
+        assert_eq!(children.last().unwrap().get_name(), Some("a".into()));
+    });
+
+    let files = &[
+        ("a.cpp", "struct A { int a; };"),
+        ("b.cpp", "struct A { int a; int b; };\nvoid f();"),
+    ];
+
+    with_temporary_files(files, |_, fs| {
+        let index = Index::new(&clang, false, false);
+        let a = index.parser(&fs[0]).parse().unwrap();
+        let b = index.parser(&fs[1]).parse().unwrap();
+
+        let changes = ast_diff(&a, &b);
+        assert_eq!(changes.len(), 2);
+        let (old, new) = match changes[0] {
+            AstChange::Modified(old, new) => (old, new),
+            _ => unreachable!(),
+        };
+        assert_eq!(old.get_name(), Some("A".into()));
+        assert_eq!(new.get_name(), Some("A".into()));
+        let added = match changes[1] {
+            AstChange::Added(added) => added,
+            _ => unreachable!(),
+        };
+        assert_eq!(added.get_name(), Some("f".into()));
+
+        let changes = ast_diff_with_depth(&a, &b, 1);
+        assert_eq!(changes.len(), 3);
+        let added = match changes[1] {
+            AstChange::Added(added) => added,
+            _ => unreachable!(),
+        };
+        assert_eq!(added.get_name(), Some("b".into()));
+    });
+
+    with_translation_unit(&clang, "test.cpp", "int a = 322;", &[], |d, _, tu| {
+        assert_eq!(tu.get_file(d.join("test.c")), None);
+    });
+
+    with_translation_unit(&clang, "test.cpp", "int a = 322;", &[], |_, _, tu| {
+        let usage = tu.get_memory_usage();
+        assert_eq!(usage.get(&MemoryUsage::Selectors), Some(&0));
     });
 
     with_translation_unit(&clang, "test.cpp", "int a = 322;", &[], |_, f, tu| {
         let _ = tu.reparse(&[Unsaved::new(f, "int a = 644;")]).unwrap();
     });
 
+    with_translation_unit(&clang, "test.cpp", "int a = 322;", &[], |_, f, tu| {
+        let mut severities = vec![];
+        let unsaved = &[Unsaved::new(f, "#warning \"reparsed\"\nint a = 644;")];
+        let _ = tu.reparse_with(unsaved, |d| severities.push(d.get_severity())).unwrap();
+        assert_eq!(severities, &[diagnostic::Severity::Warning]);
+    });
+
     // Type ______________________________________
 
     with_entity(&clang, "int a = 322;", |e| {
@@ -1055,6 +2599,76 @@ fn test() {
         assert_eq!(ts[1].get_sizeof(), Ok(size * 2));
     });
 
+    let source = "
+        struct A;
+        struct B;
+        struct A { int a; int b; };
+    ";
+
+    with_types(&clang, source, |ts| {
+        let size = mem::size_of::<c_int>();
+        assert_eq!(ts[0].get_sizeof_via_definition(), Ok(size * 2));
+        assert_eq!(ts[1].get_sizeof_via_definition(), Err(SizeofError::Incomplete));
+    });
+
+    let source = "
+        struct A { int a; };
+        typedef struct A B;
+        typedef B C;
+        int d;
+    ";
+
+    with_types(&clang, source, |ts| {
+        let usr = ts[0].get_usr();
+        assert!(usr.is_some());
+        assert_eq!(ts[1].get_usr(), usr);
+        assert_eq!(ts[2].get_usr(), usr);
+        assert_eq!(ts[3].get_usr(), None);
+    });
+
+    let source = "
+        int a[3][4];
+        extern int b[][2];
+        typedef int C[5];
+        int d;
+    ";
+
+    with_types(&clang, source, |ts| {
+        assert_eq!(ts[0].get_array_dimensions(), Some(vec![Some(3), Some(4)]));
+        assert_eq!(ts[0].get_array_element_base().map(|t| t.get_kind()), Some(TypeKind::Int));
+        assert_eq!(ts[1].get_array_dimensions(), Some(vec![None, Some(2)]));
+        assert_eq!(ts[2].get_array_dimensions(), Some(vec![Some(5)]));
+        assert_eq!(ts[3].get_array_dimensions(), None);
+        assert_eq!(ts[3].get_array_element_base(), None);
+    });
+
+    let source = "
+        struct A { int a; };
+        enum B { C };
+        typedef double D;
+        int e;
+        D f;
+        long double g;
+        int h[2];
+        int* i;
+    ";
+
+    with_types(&clang, source, |ts| {
+        let categories = ts.iter().map(|t| {
+            (t.is_aggregate(), t.is_floating_point(), t.is_scalar())
+        }).collect::<Vec<_>>();
+        assert_eq!(categories, &[
+            (true, false, false),
+            (false, false, true),
+            (false, true, true),
+            (false, false, true),
+            (false, true, true),
+            (false, true, true),
+            (true, false, false),
+            (false, false, true),
+        ]);
+    });
+
     let source = "
         int integer = 322;
         void a() { }
@@ -1065,6 +2679,36 @@ fn test() {
         assert_eq!(ts[1].get_calling_convention(), Some(CallingConvention::Cdecl));
     });
 
+    let source = "
+        int integer = 322;
+        void (*f)(int, ...) noexcept;
+    ";
+
+    with_translation_unit(&clang, "test.cpp", source, &["-std=c++17"], |_, _, tu| {
+        #[cfg(feature="clang_5_0")]
+        fn test_exception_specification(info: &FunctionTypeInfo) {
+            let specification = Some(ExceptionSpecification::BasicNoexcept);
+            assert_eq!(info.exception_specification, specification);
+        }
+
+        #[cfg(not(feature="clang_5_0"))]
+        fn test_exception_specification(_: &FunctionTypeInfo) { }
+
+        let children = tu.get_entity().get_children();
+        assert_eq!(children.len(), 2);
+
+        assert_eq!(children[0].get_type().unwrap().get_function_info(), None);
+
+        let type_ = children[1].get_type().unwrap().get_pointee_type().unwrap();
+        let info = type_.get_function_info().unwrap();
+        assert_eq!(info.result_type.get_kind(), TypeKind::Void);
+        assert_eq!(info.argument_types.len(), 1);
+        assert_eq!(info.argument_types[0].get_kind(), TypeKind::Int);
+        assert!(info.variadic);
+        assert_eq!(info.calling_convention, Some(CallingConvention::Cdecl));
+        test_exception_specification(&info);
+    });
+
     let source = "
         int integer;
         typedef int Integer;
@@ -1114,6 +2758,42 @@ fn test() {
         test_get_elaborated_type(&ts);
     });
 
+    let source = "
+        struct S { };
+        struct S s;
+    ";
+
+    with_types(&clang, source, |ts| {
+        #[cfg(feature="clang_3_9")]
+        fn test_strip_elaborated<'tu>(types: &[Type<'tu>]) {
+            assert_eq!(types[0].get_kind(), TypeKind::Record);
+            assert_eq!(types[0].strip_elaborated(), types[0]);
+            assert_eq!(types[1].get_kind(), TypeKind::Elaborated);
+            assert_eq!(types[1].strip_elaborated(), types[0]);
+        }
+
+        #[cfg(not(feature="clang_3_9"))]
+        fn test_strip_elaborated<'tu>(_: &[Type<'tu>]) { }
+
+        test_strip_elaborated(&ts);
+    });
+
+    let source = "
+        enum E : short { };
+        E e;
+        int i;
+    ";
+
+    with_entity(&clang, source, |e| {
+        let children = e.get_children();
+        assert_eq!(children.len(), 3);
+
+        let type_ = children[1].get_type().unwrap();
+        let underlying = type_.get_enum_underlying_type().unwrap();
+        assert_eq!(underlying.get_kind(), TypeKind::Short);
+        assert_eq!(children[2].get_type().unwrap().get_enum_underlying_type(), None);
+    });
+
     let source = "
         int integer = 322;
         int array[3] = { 3, 2, 2 };
@@ -1147,6 +2827,35 @@ fn test() {
         test_get_exception_specification(&ts[..]);
     });
 
+    let source = "
+        struct A { char a; int b; unsigned c : 3; double d; struct { short e; }; };
+        int f;
+    ";
+
+    with_types(&clang, source, |ts| {
+        #[cfg(feature="clang_3_7")]
+        fn test_get_field_layouts(ts: &[Type]) {
+            let layouts = ts[0].get_field_layouts().unwrap();
+            let layouts = layouts.iter().map(|l| {
+                (l.name.as_ref().map(|n| &n[..]), l.offset_bits, l.size_bits, l.align_bits)
+            }).collect::<Vec<_>>();
+            assert_eq!(layouts, &[
+                (Some("a"), 0, 8, 8),
+                (Some("b"), 32, 32, 32),
+                (Some("c"), 64, 3, 32),
+                (Some("d"), 128, 64, 64),
+                (None, 192, 16, 16),
+            ]);
+
+            assert_eq!(ts[1].get_field_layouts(), None);
+        }
+
+        #[cfg(not(feature="clang_3_7"))]
+        fn test_get_field_layouts(_: &[Type]) { }
+
+        test_get_field_layouts(&ts[..]);
+    });
+
     let source = "
         struct A { int a, b, c; };
     ";
@@ -1163,6 +2872,37 @@ fn test() {
         test_get_fields(e.get_children()[0]);
     });
 
+    let source = "
+        typedef struct { int x; } T;
+        struct S { int y; };
+        typedef struct S U;
+        T t;
+    ";
+
+    with_entity(&clang, source, |e| {
+        #[cfg(feature="clang_3_7")]
+        fn test_is_anonymous(types: &[Type]) {
+            assert!(types[0].is_anonymous());
+            assert!(!types[2].is_anonymous());
+            assert!(!types[3].is_anonymous());
+            assert!(types[4].is_anonymous());
+        }
+
+        #[cfg(not(feature="clang_3_7"))]
+        fn test_is_anonymous(_: &[Type]) { }
+
+        let children = e.get_children();
+        assert_eq!(children.len(), 5);
+
+        let types = children.iter().map(|c| c.get_type().unwrap()).collect::<Vec<_>>();
+        assert_eq!(types[0].get_owning_typedef(), Some(children[1]));
+        assert_eq!(types[2].get_owning_typedef(), None);
+        assert_eq!(types[3].get_owning_typedef(), None);
+        assert_eq!(types[4].get_owning_typedef(), Some(children[1]));
+
+        test_is_anonymous(&types);
+    });
+
     let source = "
         int integer = 322;
         int* pointer = &integer;
@@ -1191,6 +2931,28 @@ fn test() {
         assert_eq!(types[2].get_ref_qualifier(), Some(RefQualifier::RValue));
     });
 
+    let source = "
+        struct A { } a;
+        union B { } b;
+        class C { } c;
+        typedef int D;
+        D d;
+    ";
+
+    with_types(&clang, source, |ts| {
+        let kinds = ts.iter().map(|t| t.get_record_kind()).collect::<Vec<_>>();
+        assert_eq!(kinds, &[
+            Some(RecordKind::Struct),
+            Some(RecordKind::Struct),
+            Some(RecordKind::Union),
+            Some(RecordKind::Union),
+            Some(RecordKind::Class),
+            Some(RecordKind::Class),
+            None,
+            None,
+        ]);
+    });
+
     let source = "
         template <typename T, int I> class Class { int member; };
         int integer = 322;
@@ -1202,6 +2964,22 @@ fn test() {
         assert_eq!(ts[1].get_template_argument_types(), Some(vec![Some(ts[0]), None]));
     });
 
+    let source = "
+        namespace std { template <typename T> class vector { T* data; }; }
+        typedef int integer;
+        std::vector<int> a;
+        std::vector<integer> b;
+        std::vector<double> c;
+        int d;
+    ";
+
+    with_types(&clang, source, |ts| {
+        assert!(ts[1].specialization_equals(&ts[2]));
+        assert!(!ts[1].specialization_equals(&ts[3]));
+        assert!(!ts[1].specialization_equals(&ts[4]));
+        assert!(!ts[4].specialization_equals(&ts[4]));
+    });
+
     let source = "
         int a;
         typedef int Integer;
@@ -1222,6 +3000,22 @@ fn test() {
         test_get_typedef_name(&ts[..]);
     });
 
+    let source = "
+        typedef int int4 __attribute__((vector_size(16)));
+        typedef float float4 __attribute__((ext_vector_type(4)));
+        int4 a;
+        float4 b;
+        int c;
+    ";
+
+    with_types(&clang, source, |ts| {
+        assert_eq!(ts[2].get_vector_byte_size(), Some(16));
+        if cfg!(feature="clang_9_0") {
+            assert_eq!(ts[3].get_vector_byte_size(), Some(16));
+        }
+        assert_eq!(ts[4].get_vector_byte_size(), None);
+    });
+
     let source = "
         int a = 322;
         const int b = 322;
@@ -1254,6 +3048,67 @@ fn test() {
         assert!(!ts[1].is_pod());
     });
 
+    let source = "
+        kernel void f(global int* g, local int* l, constant int* c) { }
+    ";
+
+    with_translation_unit(&clang, "test.cl", source, &[], |_, _, tu| {
+        #[cfg(feature="clang_5_0")]
+        fn test_get_named_address_space(types: &[Type]) {
+            assert_eq!(types[0].get_named_address_space(), AddressSpace::Global);
+            assert_eq!(types[1].get_named_address_space(), AddressSpace::Local);
+            assert_eq!(types[2].get_named_address_space(), AddressSpace::Constant);
+        }
+
+        #[cfg(not(feature="clang_5_0"))]
+        fn test_get_named_address_space(_: &[Type]) { }
+
+        let function = tu.get_entity().get_children().into_iter().last().unwrap();
+        let types = function.get_arguments().unwrap().iter().map(|a| {
+            a.get_type().unwrap().get_pointee_type().unwrap()
+        }).collect::<Vec<_>>();
+        test_get_named_address_space(&types);
+    });
+
+    let source = "
+        struct T { };
+        void f(const T& a, T* b, T&& c, T d);
+    ";
+
+    with_entity(&clang, source, |e| {
+        let function = e.get_children()[1];
+        let types = function.get_arguments().unwrap().iter().map(|a| {
+            a.get_type().unwrap()
+        }).collect::<Vec<_>>();
+
+        assert_eq!(types[0].parameter_passing(), ParameterPassing::ByLValueRef);
+        assert!(types[0].get_pointee_type().unwrap().is_const_qualified());
+        assert_eq!(types[1].parameter_passing(), ParameterPassing::ByPointer);
+        assert!(!types[1].get_pointee_type().unwrap().is_const_qualified());
+        assert_eq!(types[2].parameter_passing(), ParameterPassing::ByRValueRef);
+        assert_eq!(types[3].parameter_passing(), ParameterPassing::ByValue);
+    });
+
+    let source = "
+        typedef int A;
+        typedef A B;
+        B b;
+    ";
+
+    with_types(&clang, source, |ts| {
+        assert_eq!(ts[2].get_typedef_declaration(), ts[1].get_declaration());
+
+        let chain = ts[2].get_typedef_chain();
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[0].0, "B");
+        assert_eq!(chain[0].1.get_declaration(), ts[0].get_declaration());
+        assert_eq!(chain[1].0, "A");
+        assert_eq!(chain[1].1.get_kind(), TypeKind::Int);
+
+        assert_eq!(chain[1].1.get_typedef_declaration(), None);
+        assert!(chain[1].1.get_typedef_chain().is_empty());
+    });
+
     let source = "
         void a() { }
         void b(...) { }
@@ -1285,6 +3140,12 @@ fn test() {
             let args = ty.get_objc_type_arguments();
             assert_eq!(args.len(), 1);
             assert_eq!(args[0], e[4].get_type().unwrap());
+
+            let info = ty.get_objc_object_info().unwrap();
+            assert_eq!(info.base_type, e[1].get_type().unwrap());
+            assert_eq!(info.type_arguments, args);
+            assert_eq!(info.protocols, protocols);
+            assert_eq!(e[3].get_type().unwrap().get_objc_object_info(), None);
         }
 
         #[cfg(not(feature="clang_8_0"))]
@@ -1,4 +1,5 @@
 use clang::*;
+use clang::source::*;
 use clang::token::*;
 
 pub fn test(clang: &Clang) {
@@ -24,6 +25,13 @@ pub fn test(clang: &Clang) {
         assert_token_eq!(tokens[3], Literal, "322", 1, 9, range!(file, 1, 9, 1, 12));
         assert_token_eq!(tokens[4], Punctuation, ";", 1, 12, range!(file, 1, 12, 1, 13));
 
+        let pairs = range!(file, 1, 1, 1, 13).tokenize_with_spellings();
+        assert_eq!(pairs.len(), tokens.len());
+        for (&(token, ref spelling), other) in pairs.iter().zip(tokens.iter()) {
+            assert_eq!(token.get_range(), other.get_range());
+            assert_eq!(spelling, &other.get_spelling());
+        }
+
         fn test_annotate<'tu>(tu: &'tu TranslationUnit<'tu>, tokens: &[Token<'tu>]) {
             let declaration = tu.get_entity().get_children()[0];
             let literal = declaration.get_children()[0];
@@ -38,4 +46,121 @@ pub fn test(clang: &Clang) {
 
         test_annotate(&tu, &tokens);
     });
+
+    let source = "int add(int a, int b) { return a + b; }\nconst int x = add(1, 2); // x";
+
+    super::with_translation_unit(&clang, "test.cpp", source, &[], |_, f, tu| {
+        let file = tu.get_file(f).unwrap();
+        let tokens = tu.semantic_tokens(&file);
+
+        macro_rules! token {
+            ($line:expr, $column:expr, $length:expr, $type_:ident) => (
+                token!($line, $column, $length, $type_, false, false, false)
+            );
+
+            (
+                $line:expr, $column:expr, $length:expr, $type_:ident,
+                $declaration:expr, $definition:expr, $readonly:expr
+            ) => (
+                SemanticToken {
+                    line: $line,
+                    column: $column,
+                    length: $length,
+                    type_: SemanticTokenType::$type_,
+                    modifiers: SemanticTokenModifiers {
+                        declaration: $declaration,
+                        definition: $definition,
+                        readonly: $readonly,
+                    },
+                }
+            );
+        }
+
+        assert_eq!(tokens, &[
+            token!(1, 1, 3, Keyword),
+            token!(1, 5, 3, Function, true, true, false),
+            token!(1, 9, 3, Keyword),
+            token!(1, 13, 1, Parameter, true, true, false),
+            token!(1, 16, 3, Keyword),
+            token!(1, 20, 1, Parameter, true, true, false),
+            token!(1, 25, 6, Keyword),
+            token!(1, 32, 1, Parameter),
+            token!(1, 36, 1, Parameter),
+            token!(2, 1, 5, Keyword),
+            token!(2, 7, 3, Keyword),
+            token!(2, 11, 1, Variable, true, true, true),
+            token!(2, 15, 3, Function),
+            token!(2, 19, 1, Number),
+            token!(2, 22, 1, Number),
+            token!(2, 26, 4, Comment),
+        ]);
+    });
+
+    let source = "#define A 322\n#if A\n#endif\nstatic const int f() { if (A) { return 1; } }";
+
+    super::with_translation_unit(&clang, "test.cpp", source, &[], |_, f, tu| {
+        let file = tu.get_file(f).unwrap();
+        let range = SourceRange::new(file.get_offset_location(0), file.get_location(4, 46));
+        let tokens = range.tokenize();
+
+        let categories = tokens.iter().filter_map(|t| {
+            t.keyword_category().map(|c| (t.get_spelling(), c))
+        }).collect::<Vec<_>>();
+
+        assert_eq!(categories, &[
+            ("define".into(), KeywordCategory::Preprocessor),
+            ("if".into(), KeywordCategory::Preprocessor),
+            ("endif".into(), KeywordCategory::Preprocessor),
+            ("static".into(), KeywordCategory::Storage),
+            ("const".into(), KeywordCategory::Other),
+            ("int".into(), KeywordCategory::Type),
+            ("if".into(), KeywordCategory::Control),
+            ("return".into(), KeywordCategory::Control),
+        ]);
+
+        assert!(tokens[1].is_preprocessor_directive());
+        assert!(!tokens[2].is_preprocessor_directive());
+    });
+
+    let source = "int a = 322;\nint b = 644;";
+
+    super::with_translation_unit(&clang, "test.cpp", source, &[], |_, f, tu| {
+        let file = tu.get_file(f).unwrap();
+        fn spellings<'tu>(tokens: Vec<Token<'tu>>) -> Vec<(String, SourceLocation<'tu>)> {
+            tokens.iter().map(|t| (t.get_spelling(), t.get_location())).collect()
+        }
+
+        let tokens = spellings(file.tokenize_range(0, source.len()));
+        assert_eq!(tokens.len(), 10);
+        assert_eq!(spellings(file.tokenize_range(13, source.len())), &tokens[5..]);
+        assert_eq!(spellings(file.tokenize_range(4, 7)), &tokens[1..3]);
+        assert!(file.tokenize_range(7, 4).is_empty());
+    });
+
+    let source = "int abc = (322);\nint d; // c";
+
+    super::with_translation_unit(&clang, "test.cpp", source, &[], |_, f, tu| {
+        let file = tu.get_file(f).unwrap();
+        let spelling = |t: Option<Token>| t.map(|t| t.get_spelling());
+
+        let location = file.get_location(1, 6);
+        assert_eq!(spelling(file.get_token_at(&location)), Some("abc".into()));
+        assert_eq!(spelling(file.get_next_token(&location)), Some("=".into()));
+        assert_eq!(spelling(file.get_previous_token(&location)), Some("int".into()));
+
+        let location = file.get_location(1, 8);
+        assert_eq!(spelling(file.get_token_at(&location)), None);
+        assert_eq!(spelling(file.get_next_token(&location)), Some("=".into()));
+        assert_eq!(spelling(file.get_previous_token(&location)), Some("abc".into()));
+
+        let location = file.get_location(1, 16);
+        assert_eq!(spelling(file.get_token_at(&location)), Some(";".into()));
+        assert_eq!(spelling(file.get_next_token(&location)), Some("int".into()));
+        assert_eq!(spelling(file.get_previous_token(&location)), Some(")".into()));
+
+        let location = file.get_location(2, 10);
+        assert_eq!(spelling(file.get_token_at(&location)), Some("// c".into()));
+        assert_eq!(spelling(file.get_next_token(&location)), None);
+        assert_eq!(spelling(file.get_previous_token(&location)), Some(";".into()));
+    });
 }